@@ -1,10 +1,563 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::cmp::Ordering;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const MEMBERSHIP_TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FuzzyError {
+    InvalidDomain { min: f64, max: f64, step: f64 },
+    InvalidWeight(f64),
+    UnnamedRule(usize),
+    UnknownCondition(String),
+    UnknownConsequence(String),
+    Deserialization(String),
+    InputOutOfRange { input: f64, min: f64, max: f64 },
+    InvalidPoints(String),
+    InvalidWindow { window: usize, sample_count: usize },
+    InvalidConfidence(f64),
+    NotNormal { height: f64 },
+    InvalidInterval { min: f64, max: f64 },
+    EmptySupport,
+    NotConverged { max_depth: usize },
+    InvalidParameters(String),
+    InvalidPercentile(f64),
+    MembershipOutOfRange { x: f64, degree: f64 },
+    InvalidCsv { line: usize, message: String },
+    WeightCountMismatch { expected: usize, actual: usize },
+    NonMonotoneMapping { x: f64 },
+    InvalidFootprint { x: f64 },
+    TruthTableSize { expected: usize, actual: usize },
+    NoRules,
+    NonPositiveTotalWeight(f64),
+}
+
+impl From<std::convert::Infallible> for FuzzyError {
+    fn from(never: std::convert::Infallible) -> Self {
+        match never {}
+    }
+}
+
+impl fmt::Display for FuzzyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FuzzyError::InvalidDomain { min, max, step } => write!(
+                f,
+                "invalid domain [{}, {}] with step {}: expected min < max and 0 < step < max - min",
+                min, max, step
+            ),
+            FuzzyError::InvalidWeight(weight) => write!(f, "invalid rule weight {}", weight),
+            FuzzyError::UnnamedRule(index) => write!(f, "rule {} has no name", index),
+            FuzzyError::UnknownCondition(name) => write!(f, "no condition registered as '{}'", name),
+            FuzzyError::UnknownConsequence(name) => {
+                write!(f, "no consequence set registered as '{}'", name)
+            }
+            FuzzyError::Deserialization(message) => write!(f, "invalid rule base JSON: {}", message),
+            FuzzyError::InputOutOfRange { input, min, max } => {
+                write!(f, "input {} is outside the expected range [{}, {}]", input, min, max)
+            }
+            FuzzyError::InvalidPoints(message) => write!(f, "invalid control points: {}", message),
+            FuzzyError::InvalidWindow { window, sample_count } => write!(
+                f,
+                "window {} must be between 1 and the sample count {}",
+                window, sample_count
+            ),
+            FuzzyError::InvalidConfidence(confidence) => {
+                write!(f, "confidence {} must be within [0, 1]", confidence)
+            }
+            FuzzyError::NotNormal { height } => {
+                write!(f, "fuzzy set has height {} but must be normal", height)
+            }
+            FuzzyError::InvalidInterval { min, max } => {
+                write!(f, "invalid interval [{}, {}]: expected min < max", min, max)
+            }
+            FuzzyError::EmptySupport => write!(f, "fuzzy set has no membership over the domain"),
+            FuzzyError::NotConverged { max_depth } => {
+                write!(f, "integration did not converge within depth {}", max_depth)
+            }
+            FuzzyError::InvalidParameters(message) => write!(f, "invalid shape parameters: {}", message),
+            FuzzyError::InvalidPercentile(p) => write!(f, "percentile {} must be within [0, 1]", p),
+            FuzzyError::MembershipOutOfRange { x, degree } => {
+                write!(f, "membership {} at x = {} is outside [0, 1]", degree, x)
+            }
+            FuzzyError::InvalidCsv { line, message } => write!(f, "invalid CSV on line {}: {}", line, message),
+            FuzzyError::WeightCountMismatch { expected, actual } => {
+                write!(f, "expected {} rule weights but got {}", expected, actual)
+            }
+            FuzzyError::NonMonotoneMapping { x } => {
+                write!(f, "domain mapping is not strictly monotone near x = {}", x)
+            }
+            FuzzyError::InvalidFootprint { x } => {
+                write!(f, "lower membership exceeds upper membership at x = {}", x)
+            }
+            FuzzyError::TruthTableSize { expected, actual } => {
+                write!(f, "truth table needs {} entries but has {}", expected, actual)
+            }
+            FuzzyError::NoRules => write!(f, "rule base has no rules"),
+            FuzzyError::NonPositiveTotalWeight(total) => {
+                write!(f, "total rule weight {} must be positive", total)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FuzzyError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Domain {
+    min: f64,
+    max: f64,
+    step: f64,
+}
+
+impl Domain {
+    pub fn new(min: f64, max: f64, step: f64) -> Result<Domain, FuzzyError> {
+        if min < max && step > 0.0 && step < max - min {
+            Ok(Domain { min, max, step })
+        } else {
+            Err(FuzzyError::InvalidDomain { min, max, step })
+        }
+    }
+
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    pub fn width(&self) -> f64 {
+        self.max - self.min
+    }
+
+    pub fn sample_count(&self) -> usize {
+        if self.step > 0.0 && self.max >= self.min {
+            ((self.max - self.min) / self.step + MEMBERSHIP_TOLERANCE).floor() as usize + 1
+        } else {
+            0
+        }
+    }
+
+    fn with_step(&self, step: f64) -> Domain {
+        Domain { step, ..*self }
+    }
+
+    pub fn points(&self) -> impl DoubleEndedIterator<Item = f64> {
+        let Domain { min, step, .. } = *self;
+        (0..self.sample_count()).map(move |i| min + i as f64 * step)
+    }
+}
+
+impl Default for Domain {
+    fn default() -> Self {
+        Domain {
+            min: 0.0,
+            max: 1.0,
+            step: 0.01,
+        }
+    }
+}
+
+impl TryFrom<(f64, f64, f64)> for Domain {
+    type Error = FuzzyError;
+
+    fn try_from((min, max, step): (f64, f64, f64)) -> Result<Self, Self::Error> {
+        Domain::new(min, max, step)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConormStrategy {
+    Max,
+    AlgebraicSum,
+    BoundedSum,
+    EinsteinSum,
+}
+
+impl ConormStrategy {
+    pub fn apply(&self, a: f64, b: f64) -> f64 {
+        match self {
+            ConormStrategy::Max => f64::max(a, b),
+            ConormStrategy::AlgebraicSum => a + b - a * b,
+            ConormStrategy::BoundedSum => f64::min(1.0, a + b),
+            ConormStrategy::EinsteinSum => (a + b) / (1.0 + a * b),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TNorm {
+    Min,
+    Product,
+    Lukasiewicz,
+    EinsteinProduct,
+}
+
+impl TNorm {
+    pub fn apply(&self, a: f64, b: f64) -> f64 {
+        match self {
+            TNorm::Min => f64::min(a, b),
+            TNorm::Product => a * b,
+            TNorm::Lukasiewicz => f64::max(0.0, a + b - 1.0),
+            TNorm::EinsteinProduct => a * b / (2.0 - (a + b - a * b)),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum MembershipOp<'a> {
+    Concentrate,
+    Dilate,
+    Shift(f64),
+    ScaleDomain(f64),
+    Clip(f64),
+    Scale(f64),
+    Complement,
+    Intersect(&'a FuzzySet),
+    Union(&'a FuzzySet),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DefuzzMethod {
+    Centroid,
+    MeanOfMaximum,
+    Bisector,
+    FirstOfMaximum,
+    LastOfMaximum,
+    Percentile(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MembershipFnKind {
+    Triangular,
+    Trapezoidal,
+    Gaussian,
+    Sigmoidal,
+    Bell,
+    PiecewiseLinear,
+    Custom,
+}
 
 #[derive(Clone)]
 pub struct FuzzySet {
     name: String,
     membership_function: Arc<dyn Fn(f64) -> f64 + Send + Sync>,
+    kind: MembershipFnKind,
+    parameters: Vec<f64>,
+}
+
+const GAUSS_LEGENDRE_5: [(f64, f64); 3] = [
+    (0.0, 0.5688888888888889),
+    (0.5384693101056831, 0.47862867049936647),
+    (0.906179845938664, 0.23692688505618908),
+];
+
+const GAUSS_LEGENDRE_10: [(f64, f64); 5] = [
+    (0.14887433898163122, 0.29552422471475287),
+    (0.4333953941292472, 0.26926671930999635),
+    (0.6794095682990244, 0.21908636251598204),
+    (0.8650633666889845, 0.1494513491505806),
+    (0.9739065285171717, 0.06667134430868814),
+];
+
+const GAUSS_LEGENDRE_20: [(f64, f64); 10] = [
+    (0.07652652113349734, 0.15275338713072584),
+    (0.22778585114164507, 0.14917298647260374),
+    (0.37370608871541955, 0.14209610931838204),
+    (0.5108670019508271, 0.13168863844917664),
+    (0.636053680726515, 0.11819453196151841),
+    (0.7463319064601508, 0.10193011981724044),
+    (0.8391169718222188, 0.08327674157670475),
+    (0.912234428251326, 0.06267204833410907),
+    (0.9639719272779138, 0.04060142980038694),
+    (0.9931285991850949, 0.017614007139152118),
+];
+
+const GAUSS_LEGENDRE_50: [(f64, f64); 25] = [
+    (0.031098338327188876, 0.06217661665534726),
+    (0.09317470156008614, 0.06193606742068324),
+    (0.1548905899981459, 0.061455899590316665),
+    (0.21600723687604176, 0.06073797084177022),
+    (0.276288193779532, 0.05978505870426546),
+    (0.33550024541943735, 0.058600849813222444),
+    (0.39341431189756515, 0.05718992564772838),
+    (0.44980633497403877, 0.05555774480621252),
+    (0.5044581449074642, 0.053710621888996245),
+    (0.5571583045146501, 0.05165570306958114),
+    (0.6077029271849502, 0.04940093844946632),
+    (0.6558964656854394, 0.046955051303948434),
+    (0.7015524687068222, 0.044327504338803274),
+    (0.7444943022260685, 0.041528463090147696),
+    (0.7845558329003993, 0.03856875661258768),
+    (0.821582070859336, 0.03545983561514615),
+    (0.8554297694299461, 0.032213728223578014),
+    (0.8859679795236131, 0.028842993580535197),
+    (0.9130785566557919, 0.02536067357001239),
+    (0.936656618944878, 0.021780243170124794),
+    (0.9566109552428079, 0.018115560713489392),
+    (0.972864385106692, 0.014380822761485574),
+    (0.9853540840480058, 0.010590548383650969),
+    (0.9940319694320907, 0.006759799195745401),
+    (0.998866404420071, 0.002908622553155141),
+];
+
+fn gauss_legendre_nodes(n: usize) -> Option<Vec<(f64, f64)>> {
+    let half: &[(f64, f64)] = match n {
+        5 => &GAUSS_LEGENDRE_5,
+        10 => &GAUSS_LEGENDRE_10,
+        20 => &GAUSS_LEGENDRE_20,
+        50 => &GAUSS_LEGENDRE_50,
+        _ => return None,
+    };
+
+    Some(
+        half.iter()
+            .flat_map(|&(x, w)| {
+                if x == 0.0 {
+                    vec![(x, w)]
+                } else {
+                    vec![(-x, w), (x, w)]
+                }
+            })
+            .collect(),
+    )
+}
+
+fn simpson_pair(width: f64, values: [[f64; 2]; 3]) -> [f64; 2] {
+    let [fa, fm, fb] = values;
+    [
+        width / 6.0 * (fa[0] + 4.0 * fm[0] + fb[0]),
+        width / 6.0 * (fa[1] + 4.0 * fm[1] + fb[1]),
+    ]
+}
+
+fn adaptive_simpson_pair(
+    f: &dyn Fn(f64) -> [f64; 2],
+    (a, b): (f64, f64),
+    [fa, fm, fb]: [[f64; 2]; 3],
+    whole: [f64; 2],
+    tol: f64,
+    depth: usize,
+) -> Option<[f64; 2]> {
+    let m = (a + b) / 2.0;
+    let flm = f((a + m) / 2.0);
+    let frm = f((m + b) / 2.0);
+    let left = simpson_pair(m - a, [fa, flm, fm]);
+    let right = simpson_pair(b - m, [fm, frm, fb]);
+    let delta = [left[0] + right[0] - whole[0], left[1] + right[1] - whole[1]];
+
+    if delta[0].abs() <= 15.0 * tol && delta[1].abs() <= 15.0 * tol {
+        return Some([
+            left[0] + right[0] + delta[0] / 15.0,
+            left[1] + right[1] + delta[1] / 15.0,
+        ]);
+    }
+
+    if depth == 0 {
+        return None;
+    }
+
+    let left = adaptive_simpson_pair(f, (a, m), [fa, flm, fm], left, tol / 2.0, depth - 1)?;
+    let right = adaptive_simpson_pair(f, (m, b), [fm, frm, fb], right, tol / 2.0, depth - 1)?;
+
+    Some([left[0] + right[0], left[1] + right[1]])
+}
+
+fn interpolate_points(points: &[(f64, f64)], x: f64) -> f64 {
+    let (first, last) = match (points.first(), points.last()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return 0.0,
+    };
+
+    if x < first.0 || x > last.0 {
+        return 0.0;
+    }
+
+    let index = points.partition_point(|&(px, _)| px < x);
+
+    if index == 0 {
+        return first.1;
+    }
+
+    let (x0, y0) = points[index - 1];
+    let (x1, y1) = points[index];
+
+    if x1 == x0 {
+        y1
+    } else {
+        y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+    }
+}
+
+fn gaussian_kernel_sum(samples: &[f64], bandwidth: f64, x: f64) -> f64 {
+    samples
+        .iter()
+        .map(|&sample| (-((x - sample) / bandwidth).powi(2) / 2.0).exp())
+        .sum()
+}
+
+fn kde_peak(samples: &[f64], bandwidth: f64) -> f64 {
+    const MAX_GRID_POINTS: f64 = 100_000.0;
+    const REFINEMENT_STEPS: usize = 100;
+
+    let density = |x: f64| gaussian_kernel_sum(samples, bandwidth, x);
+    let low = samples.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = samples.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    if !low.is_finite() || !high.is_finite() || bandwidth.partial_cmp(&0.0) != Some(Ordering::Greater) {
+        return 0.0;
+    }
+
+    let intervals = ((high - low) / bandwidth * 10.0).ceil().clamp(1.0, MAX_GRID_POINTS) as usize;
+    let h = (high - low) / intervals as f64;
+    let best = (0..=intervals)
+        .map(|i| low + i as f64 * h)
+        .fold(low, |best, x| if density(x) > density(best) { x } else { best });
+
+    let (mut a, mut b) = (best - h, best + h);
+    for _ in 0..REFINEMENT_STEPS {
+        let left = a + (b - a) / 3.0;
+        let right = b - (b - a) / 3.0;
+
+        if density(left) < density(right) {
+            a = left;
+        } else {
+            b = right;
+        }
+    }
+
+    f64::max(density(best), density((a + b) / 2.0))
+}
+
+fn fcm_membership(centers: &[f64], cluster: usize, m: f64, x: f64) -> f64 {
+    let distance = (x - centers[cluster]).abs();
+
+    if distance == 0.0 {
+        return 1.0;
+    }
+
+    if centers.contains(&x) {
+        return 0.0;
+    }
+
+    let exponent = 2.0 / (m - 1.0);
+    let total: f64 = centers
+        .iter()
+        .map(|&center| (distance / (x - center).abs()).powf(exponent))
+        .sum();
+
+    1.0 / total
+}
+
+fn dot_quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+fn check_weight(weight: f64) -> Result<(), FuzzyError> {
+    if weight.is_finite() && weight >= 0.0 {
+        Ok(())
+    } else {
+        Err(FuzzyError::InvalidWeight(weight))
+    }
+}
+
+fn standard_normal(uniform: &mut impl FnMut() -> f64) -> f64 {
+    let u1 = 1.0 - uniform();
+    let u2 = uniform();
+
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+fn discrete_fourier_transform(values: &[f64], n_coeffs: usize) -> Vec<(f64, f64)> {
+    let n = values.len();
+
+    (0..usize::min(n_coeffs, n))
+        .map(|k| {
+            values.iter().enumerate().fold((0.0, 0.0), |(re, im), (j, value)| {
+                let angle = -2.0 * std::f64::consts::PI * (k * j) as f64 / n as f64;
+                (re + value * angle.cos(), im + value * angle.sin())
+            })
+        })
+        .collect()
+}
+
+fn natural_spline_second_derivatives(points: &[(f64, f64)]) -> Vec<f64> {
+    let n = points.len();
+    let mut second = vec![0.0; n];
+
+    if n < 3 {
+        return second;
+    }
+
+    let h: Vec<f64> = points.windows(2).map(|pair| pair[1].0 - pair[0].0).collect();
+    let mut diagonal = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+
+    for i in 1..n - 1 {
+        diagonal[i] = 2.0 * (h[i - 1] + h[i]);
+        rhs[i] = 6.0
+            * ((points[i + 1].1 - points[i].1) / h[i] - (points[i].1 - points[i - 1].1) / h[i - 1]);
+    }
+
+    for i in 2..n - 1 {
+        let factor = h[i - 1] / diagonal[i - 1];
+        diagonal[i] -= factor * h[i - 1];
+        rhs[i] -= factor * rhs[i - 1];
+    }
+
+    for i in (1..n - 1).rev() {
+        second[i] = (rhs[i] - h[i] * second[i + 1]) / diagonal[i];
+    }
+
+    second
+}
+
+fn evaluate_spline(points: &[(f64, f64)], second: &[f64], x: f64) -> f64 {
+    let (first, last) = match (points.first(), points.last()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return 0.0,
+    };
+
+    if x < first.0 || x > last.0 {
+        return 0.0;
+    }
+
+    let index = points.partition_point(|&(px, _)| px < x);
+
+    if index == 0 {
+        return first.1;
+    }
+
+    let (x0, y0) = points[index - 1];
+    let (x1, y1) = points[index];
+    let (m0, m1) = (second[index - 1], second[index]);
+    let h = x1 - x0;
+    let t = x - x0;
+    let slope = (y1 - y0) / h - h * (2.0 * m0 + m1) / 6.0;
+
+    (y0 + slope * t + m0 / 2.0 * t * t + (m1 - m0) / (6.0 * h) * t * t * t).clamp(0.0, 1.0)
 }
 
 impl FuzzySet {
@@ -12,157 +565,7560 @@ impl FuzzySet {
         FuzzySet {
             name: name.to_string(),
             membership_function,
+            kind: MembershipFnKind::Custom,
+            parameters: Vec::new(),
         }
     }
 
-    pub fn membership_degree(&self, x: f64) -> f64 {
-        (self.membership_function)(x)
+    fn with_shape(
+        name: &str,
+        kind: MembershipFnKind,
+        parameters: Vec<f64>,
+        membership_function: Arc<dyn Fn(f64) -> f64 + Send + Sync>,
+    ) -> Self {
+        FuzzySet {
+            name: name.to_string(),
+            membership_function,
+            kind,
+            parameters,
+        }
     }
 
-    pub fn union(&self, other: &FuzzySet) -> FuzzySet {
-        let self_func = Arc::clone(&self.membership_function);
-        let other_func = Arc::clone(&other.membership_function);
-
-        FuzzySet::new(
-            &format!("Union({}, {})", self.name, other.name),
-            Arc::new(move |x| f64::max(self_func(x), other_func(x))),
+    pub fn triangular(name: &str, a: f64, b: f64, c: f64) -> Self {
+        FuzzySet::with_shape(
+            name,
+            MembershipFnKind::Triangular,
+            vec![a, b, c],
+            Arc::new(move |x| {
+                if x == b {
+                    1.0
+                } else if x <= a || x >= c {
+                    0.0
+                } else if x < b {
+                    (x - a) / (b - a)
+                } else {
+                    (c - x) / (c - b)
+                }
+            }),
         )
     }
 
-    pub fn intersection(&self, other: &FuzzySet) -> FuzzySet {
-        let self_func = Arc::clone(&self.membership_function);
-        let other_func = Arc::clone(&other.membership_function);
-
-        FuzzySet::new(
-            &format!("Intersection({}, {})", self.name, other.name),
-            Arc::new(move |x| f64::min(self_func(x), other_func(x))),
+    pub fn trapezoidal(name: &str, a: f64, b: f64, c: f64, d: f64) -> Self {
+        FuzzySet::with_shape(
+            name,
+            MembershipFnKind::Trapezoidal,
+            vec![a, b, c, d],
+            Arc::new(move |x| {
+                if x >= b && x <= c {
+                    1.0
+                } else if x <= a || x >= d {
+                    0.0
+                } else if x < b {
+                    (x - a) / (b - a)
+                } else {
+                    (d - x) / (d - c)
+                }
+            }),
         )
     }
 
-    pub fn complement(&self) -> FuzzySet {
-        let self_func = Arc::clone(&self.membership_function);
-
-        FuzzySet::new(
-            &format!("Complement({})", self.name),
-            Arc::new(move |x| 1.0 - self_func(x)),
+    pub fn gaussian(name: &str, mean: f64, sigma: f64) -> Self {
+        FuzzySet::with_shape(
+            name,
+            MembershipFnKind::Gaussian,
+            vec![mean, sigma],
+            Arc::new(move |x| (-(x - mean).powi(2) / (2.0 * sigma * sigma)).exp()),
         )
     }
 
-    pub fn normalize(&self) -> FuzzySet {
-        let self_func = Arc::clone(&self.membership_function);
+    pub fn sigmoidal(name: &str, slope: f64, center: f64) -> Self {
+        FuzzySet::with_shape(
+            name,
+            MembershipFnKind::Sigmoidal,
+            vec![slope, center],
+            Arc::new(move |x| 1.0 / (1.0 + (-slope * (x - center)).exp())),
+        )
+    }
 
-        FuzzySet::new(
-            &format!("Normalized({})", self.name),
-            Arc::new(move |x| self_func(x) / f64::max(1.0, self_func(x))),
+    pub fn bell(name: &str, width: f64, slope: f64, center: f64) -> Self {
+        FuzzySet::with_shape(
+            name,
+            MembershipFnKind::Bell,
+            vec![width, slope, center],
+            Arc::new(move |x| 1.0 / (1.0 + ((x - center) / width).abs().powf(2.0 * slope))),
         )
     }
 
-    pub fn centroid(&self, min_val: f64, max_val: f64, step: f64) -> f64 {
-        let mut numerator = 0.0;
-        let mut denominator = 0.0;
-        let mut x = min_val;
+    pub fn kind(&self) -> MembershipFnKind {
+        self.kind
+    }
 
-        while x <= max_val {
-            let mu = self.membership_degree(x);
-            numerator += x * mu;
-            denominator += mu;
-            x += step;
-        }
+    fn shape_height(&self) -> Option<f64> {
+        let peak = match self.kind {
+            MembershipFnKind::Custom => return None,
+            MembershipFnKind::Sigmoidal => return Some(1.0),
+            MembershipFnKind::PiecewiseLinear => {
+                return Some(self.parameters.iter().skip(1).step_by(2).fold(0.0, |max, &mu| f64::max(max, mu)))
+            }
+            MembershipFnKind::Triangular | MembershipFnKind::Trapezoidal => self.parameters[1],
+            MembershipFnKind::Gaussian => self.parameters[0],
+            MembershipFnKind::Bell => self.parameters[2],
+        };
 
-        if denominator == 0.0 {
-            0.0
-        } else {
-            numerator / denominator
-        }
+        Some(self.membership_degree(peak))
     }
-}
-
-pub struct FuzzyRule {
-    condition: Box<dyn Fn(f64) -> bool + Send + Sync>,
-    consequence: FuzzySet,
-    weight: f64,
-}
 
-impl FuzzyRule {
-    pub fn new(
-        condition: Box<dyn Fn(f64) -> bool + Send + Sync>,
-        consequence: FuzzySet,
-        weight: f64,
-    ) -> Self {
-        FuzzyRule {
-            condition,
-            consequence,
-            weight,
+    pub fn parameter_vector(&self) -> Option<Vec<f64>> {
+        match self.kind {
+            MembershipFnKind::Custom => None,
+            _ => Some(self.parameters.clone()),
         }
     }
 
-    pub fn evaluate(&self, input: f64) -> Option<(FuzzySet, f64)> {
-        if (self.condition)(input) {
-            Some((self.consequence.clone(), self.weight))
-        } else {
-            None
+    pub fn from_parameters(kind: MembershipFnKind, name: &str, params: &[f64]) -> Result<FuzzySet, FuzzyError> {
+        let expected = match kind {
+            MembershipFnKind::Triangular => 3,
+            MembershipFnKind::Trapezoidal => 4,
+            MembershipFnKind::Gaussian | MembershipFnKind::Sigmoidal => 2,
+            MembershipFnKind::Bell => 3,
+            MembershipFnKind::PiecewiseLinear => {
+                if !params.len().is_multiple_of(2) {
+                    return Err(FuzzyError::InvalidParameters(format!(
+                        "piecewise-linear sets need (x, membership) pairs, got {} values",
+                        params.len()
+                    )));
+                }
+                let points: Vec<(f64, f64)> = params.chunks(2).map(|pair| (pair[0], pair[1])).collect();
+                return FuzzySet::from_points(name, &points);
+            }
+            MembershipFnKind::Custom => {
+                return Err(FuzzyError::InvalidParameters(
+                    "custom membership functions cannot be rebuilt from parameters".to_string(),
+                ))
+            }
+        };
+
+        if params.len() != expected {
+            return Err(FuzzyError::InvalidParameters(format!(
+                "{:?} sets need {} parameters, got {}",
+                kind,
+                expected,
+                params.len()
+            )));
         }
+
+        Ok(match kind {
+            MembershipFnKind::Triangular => FuzzySet::triangular(name, params[0], params[1], params[2]),
+            MembershipFnKind::Trapezoidal => {
+                FuzzySet::trapezoidal(name, params[0], params[1], params[2], params[3])
+            }
+            MembershipFnKind::Gaussian => FuzzySet::gaussian(name, params[0], params[1]),
+            MembershipFnKind::Sigmoidal => FuzzySet::sigmoidal(name, params[0], params[1]),
+            _ => FuzzySet::bell(name, params[0], params[1], params[2]),
+        })
     }
-}
 
-pub struct InferenceEngine {
-    rules: Vec<FuzzyRule>,
-}
+    pub fn from_points(name: &str, points: &[(f64, f64)]) -> Result<Self, FuzzyError> {
+        FuzzySet::validate_points(points)?;
 
-impl InferenceEngine {
-    pub fn new(rules: Vec<FuzzyRule>) -> Self {
-        InferenceEngine { rules }
+        Ok(FuzzySet::piecewise_linear(name, points.to_vec()))
     }
 
-    pub fn infer(&self, input: f64) -> String {
-        let results: Vec<(FuzzySet, f64)> = self
-            .rules
-            .iter()
-            .filter_map(|rule| rule.evaluate(input))
+    pub fn from_parametric(
+        name: &str,
+        x_fn: impl Fn(f64) -> f64,
+        mu_fn: impl Fn(f64) -> f64,
+        n_points: usize,
+    ) -> Result<Self, FuzzyError> {
+        let mut points: Vec<(f64, f64)> = (0..n_points)
+            .map(|i| {
+                let t = if n_points > 1 { i as f64 / (n_points - 1) as f64 } else { 0.0 };
+                (x_fn(t), mu_fn(t))
+            })
             .collect();
+        points.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
 
-        self.aggregate_results(&results)
+        FuzzySet::from_points(name, &points)
     }
 
-    fn aggregate_results(&self, results: &[(FuzzySet, f64)]) -> String {
-        if results.is_empty() {
-            return "Low Priority".to_string();
+    pub fn cubic_spline_interpolation(name: &str, points: &[(f64, f64)]) -> Result<Self, FuzzyError> {
+        FuzzySet::validate_points(points)?;
+
+        if points.windows(2).any(|pair| pair[1].0 == pair[0].0) {
+            return Err(FuzzyError::InvalidPoints(
+                "spline points must have strictly increasing x".to_string(),
+            ));
         }
 
-        let mut total_weight = 0.0;
-        let mut weighted_sum = 0.0;
+        let points = points.to_vec();
+        let second = natural_spline_second_derivatives(&points);
 
-        for (result, weight) in results {
-            weighted_sum += self.priority_mapping(&result.name) * weight;
-            total_weight += weight;
+        Ok(FuzzySet::new(
+            name,
+            Arc::new(move |x| evaluate_spline(&points, &second, x)),
+        ))
+    }
+
+    fn validate_points(points: &[(f64, f64)]) -> Result<(), FuzzyError> {
+        if points.is_empty() {
+            return Err(FuzzyError::InvalidPoints("at least one point is required".to_string()));
         }
 
-        if total_weight > 0.0 {
-            self.reverse_priority_mapping(weighted_sum / total_weight)
-        } else {
-            "Low Priority".to_string()
+        if let Some(&(x, mu)) = points
+            .iter()
+            .find(|(x, mu)| !x.is_finite() || !(0.0..=1.0).contains(mu))
+        {
+            return Err(FuzzyError::InvalidPoints(format!(
+                "point ({}, {}) must have a finite x and a membership in [0, 1]",
+                x, mu
+            )));
         }
-    }
 
-    fn priority_mapping(&self, priority: &str) -> f64 {
-        match priority {
-            "Urgent" => 3.0,
-            "High Priority" => 2.0,
-            "Medium Priority" => 1.0,
-            _ => 0.0,
+        if points.windows(2).any(|pair| pair[1].0 < pair[0].0) {
+            return Err(FuzzyError::InvalidPoints("points must be sorted by x".to_string()));
         }
+
+        Ok(())
     }
 
-    fn reverse_priority_mapping(&self, score: f64) -> String {
-        match score.partial_cmp(&2.5) {
-            Some(Ordering::Greater) | Some(Ordering::Equal) => "Urgent".to_string(),
-            _ => match score.partial_cmp(&1.5) {
-                Some(Ordering::Greater) | Some(Ordering::Equal) => "High Priority".to_string(),
-                _ => match score.partial_cmp(&0.5) {
-                    Some(Ordering::Greater) | Some(Ordering::Equal) => "Medium Priority".to_string(),
-                    _ => "Low Priority".to_string(),
-                },
-            },
+    pub fn from_kde(name: &str, samples: &[f64], bandwidth: f64) -> FuzzySet {
+        let samples = samples.to_vec();
+        let peak = kde_peak(&samples, bandwidth);
+
+        FuzzySet::new(
+            name,
+            Arc::new(move |x| {
+                if peak > 0.0 {
+                    gaussian_kernel_sum(&samples, bandwidth, x) / peak
+                } else {
+                    0.0
+                }
+            }),
+        )
+    }
+
+    pub fn fuzzy_c_means(
+        data: &[f64],
+        n_clusters: usize,
+        m: f64,
+        max_iter: usize,
+        tol: f64,
+    ) -> Result<Vec<FuzzySet>, FuzzyError> {
+        if n_clusters == 0 || data.len() < n_clusters {
+            return Err(FuzzyError::InvalidParameters(format!(
+                "cannot form {} clusters from {} data points",
+                n_clusters,
+                data.len()
+            )));
         }
+
+        if m.partial_cmp(&1.0) != Some(Ordering::Greater) {
+            return Err(FuzzyError::InvalidParameters(format!("fuzziness {} must be greater than 1", m)));
+        }
+
+        let mut sorted = data.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        let mut centers: Vec<f64> = (0..n_clusters)
+            .map(|i| sorted[(2 * i + 1) * sorted.len() / (2 * n_clusters)])
+            .collect();
+
+        for _ in 0..max_iter {
+            let updated: Vec<f64> = (0..n_clusters)
+                .map(|cluster| {
+                    let (numerator, denominator) = data.iter().fold((0.0, 0.0), |(num, den), &x| {
+                        let weight = fcm_membership(&centers, cluster, m, x).powf(m);
+                        (num + weight * x, den + weight)
+                    });
+
+                    if denominator > 0.0 {
+                        numerator / denominator
+                    } else {
+                        centers[cluster]
+                    }
+                })
+                .collect();
+
+            let shift = updated
+                .iter()
+                .zip(&centers)
+                .map(|(new, old)| (new - old).abs())
+                .fold(0.0, f64::max);
+
+            centers = updated;
+
+            if shift <= tol {
+                break;
+            }
+        }
+
+        centers.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        Ok((0..n_clusters)
+            .map(|cluster| {
+                let centers = centers.clone();
+
+                FuzzySet::new(
+                    &format!("Cluster {}", cluster),
+                    Arc::new(move |x| fcm_membership(&centers, cluster, m, x)),
+                )
+            })
+            .collect())
+    }
+
+    pub fn parameter_sensitivity_band(
+        &self,
+        param_index: usize,
+        delta: f64,
+    ) -> Result<(FuzzySet, FuzzySet), FuzzyError> {
+        let params = self.parameter_vector().ok_or_else(|| {
+            FuzzyError::InvalidParameters("custom membership functions have no parameters to perturb".to_string())
+        })?;
+
+        if param_index >= params.len() {
+            return Err(FuzzyError::InvalidParameters(format!(
+                "parameter index {} is out of range for {} parameters",
+                param_index,
+                params.len()
+            )));
+        }
+
+        let perturb = |offset: f64| {
+            let mut perturbed = params.clone();
+            perturbed[param_index] += offset;
+            FuzzySet::from_parameters(self.kind, &self.name, &perturbed)
+        };
+        let plus = perturb(delta)?;
+        let minus = perturb(-delta)?;
+
+        Ok((
+            plus.compose(&minus, f64::min, &format!("Lower({})", self.name)),
+            plus.compose(&minus, f64::max, &format!("Upper({})", self.name)),
+        ))
+    }
+
+    pub fn optimize_parameters(
+        &mut self,
+        training_data: &[(f64, f64)],
+        learning_rate: f64,
+        epochs: usize,
+    ) -> Result<Vec<f64>, FuzzyError> {
+        let mut params = self.parameter_vector().ok_or_else(|| {
+            FuzzyError::InvalidParameters("custom membership functions have no parameters to optimize".to_string())
+        })?;
+        let kind = self.kind;
+        let name = self.name.clone();
+        let is_membership = |i: usize| kind == MembershipFnKind::PiecewiseLinear && i % 2 == 1;
+
+        let loss = |params: &[f64]| -> Result<f64, FuzzyError> {
+            let set = FuzzySet::from_parameters(kind, &name, params)?;
+            let squared_error: f64 = training_data
+                .iter()
+                .map(|&(x, target)| (set.membership_degree(x) - target).powi(2))
+                .sum();
+            Ok(squared_error / training_data.len().max(1) as f64)
+        };
+
+        let mut losses = Vec::with_capacity(epochs);
+
+        for _ in 0..epochs {
+            let current = loss(&params)?;
+            losses.push(current);
+
+            let mut gradient = vec![0.0; params.len()];
+            for (i, slot) in gradient.iter_mut().enumerate() {
+                let mut h = 1e-6 * f64::max(1.0, params[i].abs());
+                if is_membership(i) && params[i] + h > 1.0 {
+                    h = -h;
+                }
+                let mut perturbed = params.clone();
+                perturbed[i] += h;
+                *slot = (loss(&perturbed)? - current) / h;
+            }
+
+            for (i, (param, slope)) in params.iter_mut().zip(gradient).enumerate() {
+                *param -= learning_rate * slope;
+                if is_membership(i) {
+                    *param = param.clamp(0.0, 1.0);
+                }
+            }
+        }
+
+        *self = FuzzySet::from_parameters(kind, &name, &params)?;
+        Ok(losses)
+    }
+
+    fn piecewise_linear(name: &str, points: Vec<(f64, f64)>) -> Self {
+        let parameters = points.iter().flat_map(|&(x, mu)| [x, mu]).collect();
+
+        FuzzySet::with_shape(
+            name,
+            MembershipFnKind::PiecewiseLinear,
+            parameters,
+            Arc::new(move |x| interpolate_points(&points, x)),
+        )
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn membership_degree(&self, x: f64) -> f64 {
+        (self.membership_function)(x)
+    }
+
+    pub fn membership_ratio(&self, other: &FuzzySet) -> impl Fn(f64) -> f64 + Send + Sync {
+        let self_func = Arc::clone(&self.membership_function);
+        let other_func = Arc::clone(&other.membership_function);
+
+        move |x| {
+            let numerator = self_func(x);
+            let denominator = other_func(x);
+
+            match (numerator == 0.0, denominator == 0.0) {
+                (true, true) => 1.0,
+                (false, true) => f64::MAX,
+                _ => f64::min(numerator / denominator, f64::MAX),
+            }
+        }
+    }
+
+    pub fn compose<F: Fn(f64, f64) -> f64 + Send + Sync + 'static>(
+        &self,
+        other: &FuzzySet,
+        op: F,
+        name: &str,
+    ) -> FuzzySet {
+        let self_func = Arc::clone(&self.membership_function);
+        let other_func = Arc::clone(&other.membership_function);
+
+        FuzzySet::new(name, Arc::new(move |x| op(self_func(x), other_func(x))))
+    }
+
+    pub fn membership_degree_batch(&self, xs: &[f64]) -> Vec<f64> {
+        let mut degrees = Vec::with_capacity(xs.len());
+        degrees.extend(xs.iter().map(|&x| self.membership_degree(x)));
+        degrees
+    }
+
+    pub fn membership_degree_par_batch(&self, xs: &[f64]) -> Vec<f64> {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk_size = xs.len().div_ceil(threads).max(1);
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = xs
+                .chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || self.membership_degree_batch(chunk)))
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("membership function panicked"))
+                .collect()
+        })
+    }
+
+    pub fn fuzzify(&self, x: f64) -> f64 {
+        self.membership_degree(x)
+    }
+
+    pub fn union(&self, other: &FuzzySet) -> FuzzySet {
+        self.compose(other, f64::max, &format!("Union({}, {})", self.name, other.name))
+    }
+
+    pub fn union_all(name: &str, sets: &[&FuzzySet]) -> FuzzySet {
+        FuzzySet::aggregate(name, sets, ConormStrategy::Max)
+    }
+
+    pub fn union_then_normalize(name: &str, sets: &[&FuzzySet], domain: &Domain) -> FuzzySet {
+        let union = FuzzySet::union_all(name, sets);
+        let height = union.height(domain);
+
+        if height <= 0.0 {
+            return union;
+        }
+
+        let union_func = Arc::clone(&union.membership_function);
+
+        FuzzySet::new(name, Arc::new(move |x| f64::min(1.0, union_func(x) / height)))
+    }
+
+    pub fn aggregate(name: &str, sets: &[&FuzzySet], strategy: ConormStrategy) -> FuzzySet {
+        let weighted: Vec<(&FuzzySet, f64)> = sets.iter().map(|&set| (set, 1.0)).collect();
+        FuzzySet::aggregate_with_weights(name, &weighted, strategy)
+    }
+
+    pub fn aggregate_with_weights(
+        name: &str,
+        sets: &[(&FuzzySet, f64)],
+        strategy: ConormStrategy,
+    ) -> FuzzySet {
+        let funcs: Vec<_> = sets
+            .iter()
+            .map(|(set, weight)| (Arc::clone(&set.membership_function), *weight))
+            .collect();
+
+        FuzzySet::new(
+            name,
+            Arc::new(move |x| {
+                funcs
+                    .iter()
+                    .fold(0.0, |acc, (func, weight)| strategy.apply(acc, func(x) * weight))
+            }),
+        )
+    }
+
+    pub fn tnorm_reduce<'a, I: Iterator<Item = &'a FuzzySet>>(
+        name: &str,
+        sets: I,
+        norm: TNorm,
+    ) -> Option<FuzzySet> {
+        let funcs: Vec<_> = sets.map(|set| Arc::clone(&set.membership_function)).collect();
+
+        if funcs.is_empty() {
+            return None;
+        }
+
+        Some(FuzzySet::new(
+            name,
+            Arc::new(move |x| {
+                funcs[1..]
+                    .iter()
+                    .fold(funcs[0](x), |acc, func| norm.apply(acc, func(x)))
+            }),
+        ))
+    }
+
+    pub fn t_norm_chain(name: &str, sets: &[(&FuzzySet, TNorm)]) -> FuzzySet {
+        let funcs: Vec<_> = sets
+            .iter()
+            .map(|(set, norm)| (Arc::clone(&set.membership_function), *norm))
+            .collect();
+
+        FuzzySet::new(
+            name,
+            Arc::new(move |x| match funcs.split_first() {
+                Some(((first, _), rest)) => rest
+                    .iter()
+                    .fold(first(x), |acc, (func, norm)| norm.apply(acc, func(x))),
+                None => 1.0,
+            }),
+        )
+    }
+
+    pub fn conorm_reduce<'a, I: Iterator<Item = &'a FuzzySet>>(
+        name: &str,
+        sets: I,
+        strategy: ConormStrategy,
+    ) -> Option<FuzzySet> {
+        let funcs: Vec<_> = sets.map(|set| Arc::clone(&set.membership_function)).collect();
+
+        if funcs.is_empty() {
+            return None;
+        }
+
+        Some(FuzzySet::new(
+            name,
+            Arc::new(move |x| {
+                funcs[1..]
+                    .iter()
+                    .fold(funcs[0](x), |acc, func| strategy.apply(acc, func(x)))
+            }),
+        ))
+    }
+
+    pub fn intersection(&self, other: &FuzzySet) -> FuzzySet {
+        self.compose(other, f64::min, &format!("Intersection({}, {})", self.name, other.name))
+    }
+
+    pub fn complement(&self) -> FuzzySet {
+        let self_func = Arc::clone(&self.membership_function);
+
+        FuzzySet::new(
+            &format!("Complement({})", self.name),
+            Arc::new(move |x| 1.0 - self_func(x)),
+        )
+    }
+
+    pub fn product(&self, other: &FuzzySet) -> FuzzySet {
+        self.compose(other, |a, b| f64::min(1.0, a * b), &format!("Product({}, {})", self.name, other.name))
+    }
+
+    pub fn product_unclamped(&self, other: &FuzzySet) -> FuzzySet {
+        self.compose(other, |a, b| a * b, &format!("Product({}, {})", self.name, other.name))
+    }
+
+    pub fn intersection_product(&self, other: &FuzzySet) -> FuzzySet {
+        self.product(other)
+    }
+
+    pub fn intersection_lukasiewicz(&self, other: &FuzzySet) -> FuzzySet {
+        self.compose(other, |a, b| f64::max(0.0, a + b - 1.0), &format!("LukasiewiczIntersection({}, {})", self.name, other.name))
+    }
+
+    pub fn sum_bounded(&self, other: &FuzzySet) -> FuzzySet {
+        self.compose(other, |a, b| f64::min(1.0, a + b), &format!("BoundedSum({}, {})", self.name, other.name))
+    }
+
+    pub fn sum_algebraic(&self, other: &FuzzySet) -> FuzzySet {
+        self.compose(other, |a, b| a + b - a * b, &format!("AlgebraicSum({}, {})", self.name, other.name))
+    }
+
+    pub fn concentrate(&self) -> FuzzySet {
+        let self_func = Arc::clone(&self.membership_function);
+
+        FuzzySet::new(
+            &format!("Concentrated({})", self.name),
+            Arc::new(move |x| self_func(x).powi(2)),
+        )
+    }
+
+    pub fn dilate(&self) -> FuzzySet {
+        let self_func = Arc::clone(&self.membership_function);
+
+        FuzzySet::new(
+            &format!("Dilated({})", self.name),
+            Arc::new(move |x| self_func(x).sqrt()),
+        )
+    }
+
+    pub fn clip(&self, alpha: f64) -> FuzzySet {
+        let self_func = Arc::clone(&self.membership_function);
+
+        FuzzySet::new(
+            &format!("Clipped({}, {})", self.name, alpha),
+            Arc::new(move |x| f64::min(self_func(x), alpha)),
+        )
+    }
+
+    pub fn scale(&self, factor: f64) -> FuzzySet {
+        let self_func = Arc::clone(&self.membership_function);
+
+        FuzzySet::new(
+            &format!("Scaled({}, {})", self.name, factor),
+            Arc::new(move |x| f64::min(1.0, self_func(x) * factor)),
+        )
+    }
+
+    pub fn scale_domain(&self, factor: f64) -> FuzzySet {
+        let self_func = Arc::clone(&self.membership_function);
+
+        FuzzySet::new(
+            &format!("DomainScaled({}, {})", self.name, factor),
+            Arc::new(move |x| self_func(x / factor)),
+        )
+    }
+
+    pub fn shift(&self, offset: f64) -> FuzzySet {
+        let self_func = Arc::clone(&self.membership_function);
+
+        FuzzySet::new(
+            &format!("Shifted({}, {})", self.name, offset),
+            Arc::new(move |x| self_func(x - offset)),
+        )
+    }
+
+    pub fn apply_threshold(&self, alpha: f64) -> FuzzySet {
+        let self_func = Arc::clone(&self.membership_function);
+
+        FuzzySet::new(
+            &format!("Thresholded({}, {})", self.name, alpha),
+            Arc::new(move |x| if self_func(x) >= alpha { 1.0 } else { 0.0 }),
+        )
+    }
+
+    pub fn apply_sigmoid_threshold(&self, steepness: f64, alpha: f64) -> FuzzySet {
+        let self_func = Arc::clone(&self.membership_function);
+
+        FuzzySet::new(
+            &format!("SigmoidThresholded({}, {})", self.name, alpha),
+            Arc::new(move |x| 1.0 / (1.0 + (-steepness * (self_func(x) - alpha)).exp())),
+        )
+    }
+
+    pub fn map_domain<F: Fn(f64) -> f64 + Send + Sync + 'static>(&self, f: F, name: &str) -> FuzzySet {
+        let self_func = Arc::clone(&self.membership_function);
+
+        FuzzySet::new(name, Arc::new(move |x| self_func(f(x))))
+    }
+
+    pub fn map_domain_checked<F: Fn(f64) -> f64 + Send + Sync + 'static>(
+        &self,
+        f: F,
+        name: &str,
+        domain: &Domain,
+    ) -> Result<FuzzySet, FuzzyError> {
+        let mapped: Vec<(f64, f64)> = domain.points().map(|x| (x, f(x))).collect();
+        let direction = mapped
+            .first()
+            .zip(mapped.get(1))
+            .and_then(|(first, second)| second.1.partial_cmp(&first.1));
+
+        if let Some(pair) = mapped
+            .windows(2)
+            .find(|pair| direction == Some(Ordering::Equal) || pair[1].1.partial_cmp(&pair[0].1) != direction)
+        {
+            return Err(FuzzyError::NonMonotoneMapping { x: pair[0].0 });
+        }
+
+        Ok(self.map_domain(f, name))
+    }
+
+    pub fn clip_domain(&self, new_min: f64, new_max: f64) -> Result<FuzzySet, FuzzyError> {
+        if new_min.partial_cmp(&new_max) != Some(Ordering::Less) {
+            return Err(FuzzyError::InvalidInterval {
+                min: new_min,
+                max: new_max,
+            });
+        }
+
+        let self_func = Arc::clone(&self.membership_function);
+
+        Ok(FuzzySet::new(
+            &format!("DomainClipped({}, [{}, {}])", self.name, new_min, new_max),
+            Arc::new(move |x| if (new_min..=new_max).contains(&x) { self_func(x) } else { 0.0 }),
+        ))
+    }
+
+    pub fn extend_domain(&self, original: &Domain) -> FuzzySet {
+        let self_func = Arc::clone(&self.membership_function);
+        let (min, max) = (original.min(), original.max());
+
+        FuzzySet::new(
+            &format!("Extended({})", self.name),
+            Arc::new(move |x| if (min..=max).contains(&x) { self_func(x) } else { 0.0 }),
+        )
+    }
+
+    pub fn transform_codomain(&self, f: impl Fn(f64) -> f64 + Send + Sync + 'static) -> FuzzySet {
+        let self_func = Arc::clone(&self.membership_function);
+
+        FuzzySet::new(
+            &format!("Transformed({})", self.name),
+            Arc::new(move |x| f(self_func(x))),
+        )
+    }
+
+    pub fn transform_codomain_checked(
+        &self,
+        f: impl Fn(f64) -> f64 + Send + Sync + 'static,
+        domain: &Domain,
+    ) -> Result<FuzzySet, FuzzyError> {
+        let transformed = self.transform_codomain(f);
+
+        for (x, degree) in transformed.sample(domain) {
+            if !(0.0..=1.0).contains(&degree) {
+                return Err(FuzzyError::MembershipOutOfRange { x, degree });
+            }
+        }
+
+        Ok(transformed)
+    }
+
+    pub fn convolve(&self, kernel: &FuzzySet, output_domain: &Domain, input_domain: &Domain) -> FuzzySet {
+        let points = output_domain
+            .points()
+            .map(|z| {
+                let mu = input_domain
+                    .points()
+                    .map(|x| f64::min(self.membership_degree(x), kernel.membership_degree(z - x)))
+                    .fold(0.0, f64::max);
+                (z, mu)
+            })
+            .collect();
+
+        FuzzySet::piecewise_linear(&format!("Convolution({}, {})", self.name, kernel.name), points)
+    }
+
+    pub fn make_convex(&self, domain: &Domain) -> FuzzySet {
+        let samples = self.sample(domain);
+        let mut suffix_max = vec![0.0; samples.len()];
+        let mut running = 0.0;
+
+        for (i, &(_, mu)) in samples.iter().enumerate().rev() {
+            running = f64::max(running, mu);
+            suffix_max[i] = running;
+        }
+
+        let mut prefix_max = 0.0;
+        let points = samples
+            .iter()
+            .zip(suffix_max)
+            .map(|(&(x, mu), right)| {
+                prefix_max = f64::max(prefix_max, mu);
+                (x, f64::min(prefix_max, right))
+            })
+            .collect();
+
+        FuzzySet::piecewise_linear(&format!("Convex({})", self.name), points)
+    }
+
+    pub fn functional_derivative<F: Fn(&FuzzySet, &Domain) -> f64>(
+        &self,
+        objective: F,
+        domain: &Domain,
+        epsilon: f64,
+    ) -> FuzzySet {
+        let samples = self.sample(domain);
+        let baseline = objective(&FuzzySet::piecewise_linear(&self.name, samples.clone()), domain);
+
+        let points = (0..samples.len())
+            .map(|i| {
+                let mut perturbed = samples.clone();
+                perturbed[i].1 += epsilon;
+                let value = objective(&FuzzySet::piecewise_linear(&self.name, perturbed), domain);
+                (samples[i].0, (value - baseline) / epsilon)
+            })
+            .collect();
+
+        FuzzySet::piecewise_linear(&format!("Derivative({})", self.name), points)
+    }
+
+    pub fn apply_noise(&self, uniform: &mut impl FnMut() -> f64, sigma: f64, domain: &Domain) -> FuzzySet {
+        let points = self
+            .sample(domain)
+            .into_iter()
+            .map(|(x, mu)| (x, (mu + sigma * standard_normal(uniform)).clamp(0.0, 1.0)))
+            .collect();
+
+        FuzzySet::piecewise_linear(&format!("Noisy({})", self.name), points)
+    }
+
+    pub fn extension_principle_2d(
+        a: &FuzzySet,
+        b: &FuzzySet,
+        f: impl Fn(f64, f64) -> f64,
+        domain_a: &Domain,
+        domain_b: &Domain,
+        domain_z: &Domain,
+    ) -> FuzzySet {
+        let samples_b = b.sample(domain_b);
+        let mut memberships = vec![0.0; domain_z.sample_count()];
+
+        for (x, mu_a) in a.sample(domain_a) {
+            if mu_a <= 0.0 {
+                continue;
+            }
+
+            for &(y, mu_b) in &samples_b {
+                let bin = ((f(x, y) - domain_z.min()) / domain_z.step()).round();
+
+                if bin >= 0.0 && (bin as usize) < memberships.len() {
+                    let slot = &mut memberships[bin as usize];
+                    *slot = f64::max(*slot, f64::min(mu_a, mu_b));
+                }
+            }
+        }
+
+        let points = domain_z.points().zip(memberships).collect();
+
+        FuzzySet::piecewise_linear(&format!("Extension({}, {})", a.name, b.name), points)
+    }
+
+    pub fn moving_average_smooth(&self, window: usize, domain: &Domain) -> Result<FuzzySet, FuzzyError> {
+        self.smooth_over_window(window, domain, &format!("Smoothed({})", self.name), |values| {
+            values.iter().sum::<f64>() / values.len() as f64
+        })
+    }
+
+    fn smooth_over_window(
+        &self,
+        window: usize,
+        domain: &Domain,
+        name: &str,
+        reduce: impl Fn(&[f64]) -> f64,
+    ) -> Result<FuzzySet, FuzzyError> {
+        let samples = self.sample(domain);
+
+        if window == 0 || window > samples.len() {
+            return Err(FuzzyError::InvalidWindow {
+                window,
+                sample_count: samples.len(),
+            });
+        }
+
+        let memberships: Vec<f64> = samples.iter().map(|&(_, mu)| mu).collect();
+        let half = window / 2;
+        let points = samples
+            .iter()
+            .enumerate()
+            .map(|(i, &(x, _))| {
+                let start = i.saturating_sub(half);
+                let end = usize::min(memberships.len(), i + window - half);
+                (x, reduce(&memberships[start..end]))
+            })
+            .collect();
+
+        Ok(FuzzySet::piecewise_linear(name, points))
+    }
+
+    pub fn moving_max_smooth(&self, window: usize, domain: &Domain) -> Result<FuzzySet, FuzzyError> {
+        self.smooth_over_window(window, domain, &format!("MaxSmoothed({})", self.name), |values| {
+            values.iter().copied().fold(0.0, f64::max)
+        })
+    }
+
+    pub fn gaussian_smooth(&self, sigma: f64, domain: &Domain) -> FuzzySet {
+        let samples = self.sample(domain);
+        let name = format!("GaussianSmoothed({}, {})", self.name, sigma);
+
+        if sigma <= 0.0 {
+            return FuzzySet::piecewise_linear(&name, samples);
+        }
+
+        let norm = 1.0 / (sigma * (2.0 * std::f64::consts::PI).sqrt());
+        let mut points: Vec<(f64, f64)> = samples
+            .iter()
+            .map(|&(x, _)| {
+                let mu: f64 = samples
+                    .iter()
+                    .map(|&(xj, mu_j)| {
+                        let d = x - xj;
+                        mu_j * norm * (-d * d / (2.0 * sigma * sigma)).exp() * domain.step()
+                    })
+                    .sum();
+                (x, mu)
+            })
+            .collect();
+
+        let smoothed_height = points.iter().map(|&(_, mu)| mu).fold(0.0, f64::max);
+        if self.is_normal(domain) && smoothed_height > 0.0 {
+            for point in &mut points {
+                point.1 /= smoothed_height;
+            }
+        }
+
+        FuzzySet::piecewise_linear(&name, points)
+    }
+
+    pub fn restrict(&self, observation: f64) -> f64 {
+        self.membership_degree(observation)
+    }
+
+    pub fn conditional_possibility(&self, event: &FuzzySet, domain: &Domain) -> f64 {
+        let height = self.height(domain);
+
+        if height == 0.0 {
+            return 0.0;
+        }
+
+        self.intersection(event).height(domain) / height
+    }
+
+    pub fn conditional_membership(&self, given: &FuzzySet, x: f64, domain: &Domain) -> f64 {
+        let height = given.height(domain);
+
+        if height == 0.0 {
+            return 0.0;
+        }
+
+        f64::min(self.membership_degree(x), given.membership_degree(x)) / height
+    }
+
+    pub fn conditional_set(&self, given: &FuzzySet, domain: &Domain) -> FuzzySet {
+        let height = given.height(domain);
+        let self_func = Arc::clone(&self.membership_function);
+        let given_func = Arc::clone(&given.membership_function);
+
+        FuzzySet::new(
+            &format!("Conditional({} | {})", self.name, given.name),
+            Arc::new(move |x| {
+                if height == 0.0 {
+                    0.0
+                } else {
+                    f64::min(self_func(x), given_func(x)) / height
+                }
+            }),
+        )
+    }
+
+    pub fn to_possibility_distribution(&self, domain: &Domain) -> Result<PossibilityDistribution, FuzzyError> {
+        PossibilityDistribution::new(self.clone(), domain)
+    }
+
+    pub fn compose_with(&self, ops: &[MembershipOp]) -> FuzzySet {
+        ops.iter().fold(self.clone(), |set, op| match *op {
+            MembershipOp::Concentrate => set.concentrate(),
+            MembershipOp::Dilate => set.dilate(),
+            MembershipOp::Shift(offset) => set.shift(offset),
+            MembershipOp::ScaleDomain(factor) => set.scale_domain(factor),
+            MembershipOp::Clip(alpha) => set.clip(alpha),
+            MembershipOp::Scale(factor) => set.scale(factor),
+            MembershipOp::Complement => set.complement(),
+            MembershipOp::Intersect(other) => set.intersection(other),
+            MembershipOp::Union(other) => set.union(other),
+        })
+    }
+
+    pub fn normalize(&self) -> FuzzySet {
+        let self_func = Arc::clone(&self.membership_function);
+
+        FuzzySet::new(
+            &format!("Normalized({})", self.name),
+            Arc::new(move |x| self_func(x) / f64::max(1.0, self_func(x))),
+        )
+    }
+
+    pub fn resample(&self, new_domain: &Domain) -> FuzzySet {
+        FuzzySet::piecewise_linear(&format!("Resampled({})", self.name), self.sample(new_domain))
+    }
+
+    pub fn align_to_grid(&self, domain: &Domain) -> FuzzySet {
+        self.resample(domain)
+    }
+
+    pub fn discretize_and_reconstruct(&self, domain: &Domain) -> (FuzzySet, f64) {
+        let reconstructed = self.resample(domain);
+
+        let max_error = domain
+            .points()
+            .flat_map(|x| [x, x + domain.step() / 2.0])
+            .filter(|&x| x <= domain.max())
+            .map(|x| (self.membership_degree(x) - reconstructed.membership_degree(x)).abs())
+            .fold(0.0, f64::max);
+
+        (reconstructed, max_error)
+    }
+
+    pub fn membership_histogram(&self, n_bins: usize, domain: &Domain) -> Vec<(f64, f64, usize)> {
+        if n_bins == 0 {
+            return Vec::new();
+        }
+
+        let mut counts = vec![0; n_bins];
+
+        for mu in self.sample_membership_only(domain) {
+            let bin = (mu.clamp(0.0, 1.0) * n_bins as f64) as usize;
+            counts[usize::min(bin, n_bins - 1)] += 1;
+        }
+
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| (i as f64 / n_bins as f64, (i + 1) as f64 / n_bins as f64, count))
+            .collect()
+    }
+
+    pub fn checksum(&self, domain: &Domain) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        domain
+            .points()
+            .flat_map(|x| self.membership_degree(x).to_bits().to_le_bytes())
+            .fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+    }
+
+    pub fn ascii_plot(&self, domain: &Domain, width: usize, height: usize) -> String {
+        if width == 0 || height == 0 {
+            return String::new();
+        }
+
+        let column_step = if width > 1 {
+            domain.width() / (width - 1) as f64
+        } else {
+            0.0
+        };
+
+        let rows: Vec<usize> = (0..width)
+            .map(|column| {
+                let x = domain.min() + column as f64 * column_step;
+                let mu = self.membership_degree(x).clamp(0.0, 1.0);
+                ((1.0 - mu) * (height - 1) as f64).round() as usize
+            })
+            .collect();
+
+        let mut plot: String = (0..height)
+            .map(|line| {
+                rows.iter()
+                    .map(|&row| if row == line { '*' } else { ' ' })
+                    .chain(std::iter::once('\n'))
+                    .collect::<String>()
+            })
+            .collect();
+
+        let min_label = format!("{}", domain.min());
+        let max_label = format!("{}", domain.max());
+        let padding = width.saturating_sub(min_label.len() + max_label.len()).max(1);
+        plot.push_str(&format!("{}{}{}\n", min_label, " ".repeat(padding), max_label));
+
+        plot
+    }
+
+    pub fn sample(&self, domain: &Domain) -> Vec<(f64, f64)> {
+        domain
+            .points()
+            .map(|x| (x, self.membership_degree(x)))
+            .collect()
+    }
+
+    pub fn sample_membership_only(&self, domain: &Domain) -> Vec<f64> {
+        domain.points().map(|x| self.membership_degree(x)).collect()
+    }
+
+    pub fn moment(&self, n: u32, center: f64, domain: &Domain) -> f64 {
+        let mut numerator = 0.0;
+        let mut denominator = 0.0;
+
+        for (x, mu) in self.sample(domain) {
+            numerator += (x - center).powi(n as i32) * mu;
+            denominator += mu;
+        }
+
+        if denominator == 0.0 {
+            0.0
+        } else {
+            numerator / denominator
+        }
+    }
+
+    pub fn centroid(&self, domain: &Domain) -> f64 {
+        self.moment(1, 0.0, domain)
+    }
+
+    pub fn centroid_at_alpha(&self, alpha: f64, domain: &Domain) -> Option<f64> {
+        let mut numerator = 0.0;
+        let mut denominator = 0.0;
+
+        for (x, mu) in self.sample(domain) {
+            if mu >= alpha {
+                numerator += x * mu;
+                denominator += mu;
+            }
+        }
+
+        if denominator == 0.0 {
+            None
+        } else {
+            Some(numerator / denominator)
+        }
+    }
+
+    pub fn centroids_at_alphas(&self, alphas: &[f64], domain: &Domain) -> Vec<Option<f64>> {
+        alphas
+            .iter()
+            .map(|&alpha| self.centroid_at_alpha(alpha, domain))
+            .collect()
+    }
+
+    pub fn multi_centroid(&self, domain: &Domain) -> Vec<f64> {
+        let samples = self.sample(domain);
+
+        self.to_crisp_intervals(&[MEMBERSHIP_TOLERANCE], domain)
+            .into_iter()
+            .flat_map(|(_, intervals)| intervals)
+            .filter_map(|(left, right)| {
+                let (numerator, denominator) = samples
+                    .iter()
+                    .filter(|&&(x, _)| x >= left && x <= right)
+                    .fold((0.0, 0.0), |(num, den), &(x, mu)| (num + x * mu, den + mu));
+
+                if denominator == 0.0 {
+                    None
+                } else {
+                    Some(numerator / denominator)
+                }
+            })
+            .collect()
+    }
+
+    pub fn weighted_centroid(&self, weight_fn: &FuzzySet, domain: &Domain) -> f64 {
+        let mut numerator = 0.0;
+        let mut denominator = 0.0;
+
+        for x in domain.points() {
+            let mu = self.membership_degree(x) * weight_fn.membership_degree(x);
+            numerator += x * mu;
+            denominator += mu;
+        }
+
+        if denominator == 0.0 {
+            0.0
+        } else {
+            numerator / denominator
+        }
+    }
+
+    pub fn defuzzify_centroid_adaptive(
+        &self,
+        min_val: f64,
+        max_val: f64,
+        tol: f64,
+        max_depth: usize,
+    ) -> Result<f64, FuzzyError> {
+        if min_val.partial_cmp(&max_val) != Some(Ordering::Less) {
+            return Err(FuzzyError::InvalidInterval {
+                min: min_val,
+                max: max_val,
+            });
+        }
+
+        const INITIAL_PANELS: usize = 16;
+
+        let integrand = |x: f64| {
+            let mu = self.membership_degree(x);
+            [mu, x * mu]
+        };
+        let panel_width = (max_val - min_val) / INITIAL_PANELS as f64;
+        let mut totals = [0.0, 0.0];
+
+        for i in 0..INITIAL_PANELS {
+            let a = min_val + i as f64 * panel_width;
+            let b = a + panel_width;
+            let values = [integrand(a), integrand((a + b) / 2.0), integrand(b)];
+            let whole = simpson_pair(panel_width, values);
+            let panel = adaptive_simpson_pair(
+                &integrand,
+                (a, b),
+                values,
+                whole,
+                tol / INITIAL_PANELS as f64,
+                max_depth,
+            )
+            .ok_or(FuzzyError::NotConverged { max_depth })?;
+
+            totals[0] += panel[0];
+            totals[1] += panel[1];
+        }
+
+        if totals[0] == 0.0 {
+            return Err(FuzzyError::EmptySupport);
+        }
+
+        Ok(totals[1] / totals[0])
+    }
+
+    pub fn normalize_to_centroid(&self, target_centroid: f64, domain: &Domain) -> FuzzySet {
+        self.shift(target_centroid - self.centroid(domain))
+    }
+
+    pub fn membership_variance(&self, domain: &Domain) -> f64 {
+        self.moment(2, self.centroid(domain), domain)
+    }
+
+    pub fn membership_std_dev(&self, domain: &Domain) -> f64 {
+        self.membership_variance(domain).sqrt()
+    }
+
+    pub fn skewness(&self, domain: &Domain) -> f64 {
+        let std_dev = self.membership_std_dev(domain);
+
+        if std_dev == 0.0 {
+            0.0
+        } else {
+            self.moment(3, self.centroid(domain), domain) / std_dev.powi(3)
+        }
+    }
+
+    pub fn kurtosis(&self, domain: &Domain) -> f64 {
+        let variance = self.membership_variance(domain);
+
+        if variance == 0.0 {
+            0.0
+        } else {
+            self.moment(4, self.centroid(domain), domain) / variance.powi(2)
+        }
+    }
+
+    pub fn excess_kurtosis(&self, domain: &Domain) -> f64 {
+        self.kurtosis(domain) - 3.0
+    }
+
+    pub fn symmetry_index(&self, domain: &Domain) -> f64 {
+        let mode = match self.defuzzify_mom(domain) {
+            Some(mode) => mode,
+            None => return 0.0,
+        };
+
+        let reach = f64::max(mode - domain.min(), domain.max() - mode);
+        let steps = (reach / domain.step() + MEMBERSHIP_TOLERANCE).floor() as usize;
+        let degree = |x: f64| {
+            if x >= domain.min() - MEMBERSHIP_TOLERANCE && x <= domain.max() + MEMBERSHIP_TOLERANCE {
+                self.membership_degree(x)
+            } else {
+                0.0
+            }
+        };
+        let mut difference = 0.0;
+        let mut total = 0.0;
+
+        for i in 0..=steps {
+            let d = i as f64 * domain.step();
+            let (right, left) = (degree(mode + d), degree(mode - d));
+            difference += (right - left).abs();
+            total += right + left;
+        }
+
+        if total == 0.0 {
+            1.0
+        } else {
+            1.0 - difference / total
+        }
+    }
+
+    pub fn centroid_domain_sensitivity(&self, inner_domain: &Domain, outer_domain: &Domain) -> f64 {
+        (self.centroid(outer_domain) - self.centroid(inner_domain)).abs()
+    }
+
+    pub fn minimal_domain(&self, search_domain: &Domain, tol: f64, step: f64) -> Option<Domain> {
+        let (left, right) = self.tight_bounds(search_domain, tol)?;
+
+        Domain::new(left, right, step).ok()
+    }
+
+    pub fn centroid_variance(&self, domain: &Domain, n_refinements: usize) -> f64 {
+        let estimates: Vec<f64> = (0..=n_refinements)
+            .map(|level| {
+                let step = domain.step() / 2f64.powi(level as i32);
+                self.centroid(&domain.with_step(step))
+            })
+            .collect();
+
+        let mean = estimates.iter().sum::<f64>() / estimates.len() as f64;
+
+        estimates.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / estimates.len() as f64
+    }
+
+    pub fn centroid_with_error(&self, domain: &Domain, tol: f64) -> (f64, f64) {
+        const MAX_REFINEMENTS: i32 = 16;
+
+        let mut previous = self.centroid(domain);
+        let mut error = f64::INFINITY;
+
+        for level in 1..=MAX_REFINEMENTS {
+            let step = domain.step() / 2f64.powi(level);
+            let current = self.centroid(&domain.with_step(step));
+            error = (current - previous).abs();
+            previous = current;
+
+            if error <= tol {
+                break;
+            }
+        }
+
+        (previous, error)
+    }
+
+    pub fn defuzzify_centroid_gauss_legendre(
+        &self,
+        min_val: f64,
+        max_val: f64,
+        n_points: usize,
+    ) -> Result<f64, FuzzyError> {
+        if min_val.partial_cmp(&max_val) != Some(Ordering::Less) {
+            return Err(FuzzyError::InvalidInterval {
+                min: min_val,
+                max: max_val,
+            });
+        }
+
+        let nodes = gauss_legendre_nodes(n_points).ok_or_else(|| {
+            FuzzyError::InvalidParameters(format!(
+                "Gauss-Legendre tables exist for 5, 10, 20 and 50 points, not {}",
+                n_points
+            ))
+        })?;
+
+        let half_width = (max_val - min_val) / 2.0;
+        let midpoint = (max_val + min_val) / 2.0;
+        let mut numerator = 0.0;
+        let mut denominator = 0.0;
+
+        for (node, weight) in nodes {
+            let x = midpoint + half_width * node;
+            let mu = self.membership_degree(x) * weight;
+            numerator += x * mu;
+            denominator += mu;
+        }
+
+        if denominator == 0.0 {
+            Ok(0.0)
+        } else {
+            Ok(numerator / denominator)
+        }
+    }
+
+    pub fn defuzzify_centroid<D>(&self, domain: D) -> Result<f64, FuzzyError>
+    where
+        D: TryInto<Domain>,
+        FuzzyError: From<D::Error>,
+    {
+        Ok(self.centroid(&domain.try_into()?))
+    }
+
+    pub fn defuzzify_fom(&self, domain: &Domain) -> Option<f64> {
+        let height = self.max_membership(domain);
+
+        if height <= 0.0 {
+            return None;
+        }
+
+        domain
+            .points()
+            .find(|&x| (self.membership_degree(x) - height).abs() <= MEMBERSHIP_TOLERANCE)
+    }
+
+    pub fn defuzzify_lom(&self, domain: &Domain) -> Option<f64> {
+        let height = self.max_membership(domain);
+
+        if height <= 0.0 {
+            return None;
+        }
+
+        domain
+            .points()
+            .rfind(|&x| (self.membership_degree(x) - height).abs() <= MEMBERSHIP_TOLERANCE)
+    }
+
+    pub fn defuzzify_mom(&self, domain: &Domain) -> Option<f64> {
+        let first = self.defuzzify_fom(domain)?;
+        let last = self.defuzzify_lom(domain)?;
+
+        Some((first + last) / 2.0)
+    }
+
+    pub fn membership_inverse_rising(&self, mu: f64, domain: &Domain) -> Option<f64> {
+        let peak = self.defuzzify_fom(domain)?;
+        self.bisect_membership(mu, domain.min(), peak)
+    }
+
+    pub fn membership_inverse_falling(&self, mu: f64, domain: &Domain) -> Option<f64> {
+        let peak = self.defuzzify_lom(domain)?;
+        self.bisect_membership(mu, peak, domain.max())
+    }
+
+    fn bisect_membership(&self, mu: f64, mut low: f64, mut high: f64) -> Option<f64> {
+        const ITERATIONS: usize = 64;
+
+        let rising = self.membership_degree(low) <= self.membership_degree(high);
+        let (lowest, highest) = if rising {
+            (self.membership_degree(low), self.membership_degree(high))
+        } else {
+            (self.membership_degree(high), self.membership_degree(low))
+        };
+
+        if mu < lowest || mu > highest {
+            return None;
+        }
+
+        for _ in 0..ITERATIONS {
+            let mid = (low + high) / 2.0;
+
+            if (self.membership_degree(mid) < mu) == rising {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        Some((low + high) / 2.0)
+    }
+
+    pub fn defuzzify(&self, method: DefuzzMethod, domain: &Domain) -> Result<f64, FuzzyError> {
+        match method {
+            DefuzzMethod::Centroid if self.has_nonempty_support(domain) => Ok(self.centroid(domain)),
+            DefuzzMethod::Centroid => Err(FuzzyError::EmptySupport),
+            DefuzzMethod::MeanOfMaximum => self.defuzzify_mom(domain).ok_or(FuzzyError::EmptySupport),
+            DefuzzMethod::Bisector => self.percentile(0.5, domain),
+            DefuzzMethod::FirstOfMaximum => self.defuzzify_fom(domain).ok_or(FuzzyError::EmptySupport),
+            DefuzzMethod::LastOfMaximum => self.defuzzify_lom(domain).ok_or(FuzzyError::EmptySupport),
+            DefuzzMethod::Percentile(p) => self.percentile(p, domain),
+        }
+    }
+
+    pub fn local_maxima(&self, domain: &Domain) -> Vec<(f64, f64)> {
+        let samples = self.sample(domain);
+        let mut maxima = Vec::new();
+        let mut start = 0;
+
+        while start < samples.len() {
+            let mu = samples[start].1;
+            let mut end = start;
+
+            while end + 1 < samples.len() && (samples[end + 1].1 - mu).abs() <= MEMBERSHIP_TOLERANCE {
+                end += 1;
+            }
+
+            let rises = start == 0 || samples[start - 1].1 < mu;
+            let falls = end + 1 == samples.len() || samples[end + 1].1 < mu;
+
+            if rises && falls && mu > 0.0 {
+                maxima.push(samples[(start + end) / 2]);
+            }
+
+            start = end + 1;
+        }
+
+        maxima
+    }
+
+    pub fn is_unimodal(&self, domain: &Domain) -> bool {
+        self.local_maxima(domain).len() == 1
+    }
+
+    pub fn estimate_lipschitz_constant(&self, domain: &Domain) -> f64 {
+        self.sample_membership_only(domain)
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).abs() / domain.step())
+            .fold(0.0, f64::max)
+    }
+
+    pub fn is_lipschitz(&self, l: f64, domain: &Domain) -> bool {
+        self.estimate_lipschitz_constant(domain) <= l + MEMBERSHIP_TOLERANCE
+    }
+
+    pub fn aggregated_defuzzify(
+        sets: &[(&FuzzySet, f64)],
+        method: DefuzzMethod,
+        domain: &Domain,
+    ) -> Result<f64, FuzzyError> {
+        let clipped: Vec<FuzzySet> = sets.iter().map(|(set, weight)| set.clip(*weight)).collect();
+
+        FuzzySet::union_all("Aggregate", &clipped.iter().collect::<Vec<_>>()).defuzzify(method, domain)
+    }
+
+    pub fn rank_centroid(&self, other: &FuzzySet, domain: &Domain) -> Ordering {
+        self.centroid(domain)
+            .partial_cmp(&other.centroid(domain))
+            .unwrap_or(Ordering::Equal)
+    }
+
+    pub fn rank_defuzz(
+        &self,
+        other: &FuzzySet,
+        method: DefuzzMethod,
+        domain: &Domain,
+    ) -> Result<Ordering, FuzzyError> {
+        let this = self.defuzzify(method, domain)?;
+        let that = other.defuzzify(method, domain)?;
+
+        Ok(this.partial_cmp(&that).unwrap_or(Ordering::Equal))
+    }
+
+    pub fn mamdani_implication(&self, consequent: &FuzzySet) -> FuzzyRelation {
+        let antecedent = Arc::clone(&self.membership_function);
+        let consequent_func = Arc::clone(&consequent.membership_function);
+
+        FuzzyRelation::new(
+            &format!("Mamdani({}, {})", self.name, consequent.name),
+            Arc::new(move |x, y| f64::min(antecedent(x), consequent_func(y))),
+        )
+    }
+
+    pub fn larsen_implication(&self, consequent: &FuzzySet) -> FuzzyRelation {
+        let antecedent = Arc::clone(&self.membership_function);
+        let consequent_func = Arc::clone(&consequent.membership_function);
+
+        FuzzyRelation::new(
+            &format!("Larsen({}, {})", self.name, consequent.name),
+            Arc::new(move |x, y| antecedent(x) * consequent_func(y)),
+        )
+    }
+
+    pub fn support(&self, domain: &Domain) -> Vec<f64> {
+        domain.points()
+            .filter(|&x| self.membership_degree(x) > 0.0)
+            .collect()
+    }
+
+    pub fn has_nonempty_support(&self, domain: &Domain) -> bool {
+        domain.points().any(|x| self.membership_degree(x) > 0.0)
+    }
+
+    pub fn core(&self, domain: &Domain) -> Vec<f64> {
+        domain.points()
+            .filter(|&x| (self.membership_degree(x) - 1.0).abs() <= MEMBERSHIP_TOLERANCE)
+            .collect()
+    }
+
+    pub fn max_membership(&self, domain: &Domain) -> f64 {
+        domain
+            .points()
+            .map(|x| self.membership_degree(x))
+            .reduce(f64::max)
+            .unwrap_or(0.0)
+    }
+
+    pub fn min_membership(&self, domain: &Domain) -> f64 {
+        domain
+            .points()
+            .map(|x| self.membership_degree(x))
+            .reduce(f64::min)
+            .unwrap_or(0.0)
+    }
+
+    pub fn height(&self, domain: &Domain) -> f64 {
+        self.max_membership(domain)
+    }
+
+    pub fn is_normal(&self, domain: &Domain) -> bool {
+        (self.max_membership(domain) - 1.0).abs() <= MEMBERSHIP_TOLERANCE
+    }
+
+    pub fn is_subnormal(&self, domain: &Domain) -> bool {
+        self.max_membership(domain) < 1.0 - MEMBERSHIP_TOLERANCE
+    }
+
+    pub fn alpha_cut(&self, alpha: f64, domain: &Domain) -> Vec<f64> {
+        self.points_where(|_, mu| mu >= alpha, domain).collect()
+    }
+
+    pub fn count_where<P: Fn(f64, f64) -> bool>(&self, predicate: P, domain: &Domain) -> usize {
+        self.points_where(predicate, domain).count()
+    }
+
+    pub fn fraction_where<P: Fn(f64, f64) -> bool>(&self, predicate: P, domain: &Domain) -> f64 {
+        match domain.sample_count() {
+            0 => 0.0,
+            count => self.count_where(predicate, domain) as f64 / count as f64,
+        }
+    }
+
+    fn points_where<'a, P: Fn(f64, f64) -> bool + 'a>(
+        &'a self,
+        predicate: P,
+        domain: &Domain,
+    ) -> impl Iterator<Item = f64> + 'a {
+        domain
+            .points()
+            .filter(move |&x| predicate(x, self.membership_degree(x)))
+    }
+
+    pub fn alpha_cut_interval(&self, alpha: f64, domain: &Domain) -> Option<(f64, f64)> {
+        let left = domain.points().find(|&x| self.membership_degree(x) >= alpha)?;
+        let right = domain.points().rfind(|&x| self.membership_degree(x) >= alpha)?;
+
+        Some((left, right))
+    }
+
+    pub fn for_alpha_cuts<F: FnMut(f64, (f64, f64))>(&self, alphas: &[f64], domain: &Domain, mut callback: F) {
+        for &alpha in alphas {
+            if let Some(interval) = self.alpha_cut_interval(alpha, domain) {
+                callback(alpha, interval);
+            }
+        }
+    }
+
+    pub fn interval_representation(&self, alphas: &[f64], domain: &Domain) -> Vec<(f64, Option<(f64, f64)>)> {
+        alphas
+            .iter()
+            .map(|&alpha| {
+                let interval = if alpha <= 0.0 {
+                    self.bounding_box(domain)
+                } else {
+                    self.alpha_cut_interval(alpha, domain)
+                };
+                (alpha, interval)
+            })
+            .collect()
+    }
+
+    pub fn to_crisp_intervals(&self, alphas: &[f64], domain: &Domain) -> Vec<(f64, Vec<(f64, f64)>)> {
+        let samples = self.sample(domain);
+
+        alphas
+            .iter()
+            .map(|&alpha| {
+                let mut intervals = Vec::new();
+                let mut start = None;
+                let mut previous = None;
+
+                for &(x, mu) in &samples {
+                    match (mu >= alpha, start) {
+                        (true, None) => start = Some(x),
+                        (false, Some(left)) => {
+                            intervals.push((left, previous.unwrap_or(left)));
+                            start = None;
+                        }
+                        _ => {}
+                    }
+                    previous = Some(x);
+                }
+
+                if let (Some(left), Some(right)) = (start, previous) {
+                    intervals.push((left, right));
+                }
+
+                (alpha, intervals)
+            })
+            .collect()
+    }
+
+    pub fn alpha_cut_series(&self, alphas: &[f64], domain: &Domain) -> Vec<(f64, Option<(f64, f64)>)> {
+        self.interval_representation(alphas, domain)
+    }
+
+    pub fn alpha_cut_svg(&self, alphas: &[f64], domain: &Domain, width: u32, height: u32) -> String {
+        use std::fmt::Write as _;
+
+        let scale_x = |x: f64| (x - domain.min()) / domain.width() * width as f64;
+        let scale_y = |alpha: f64| (1.0 - alpha) * height as f64;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+            width, height, width, height
+        );
+
+        for (alpha, interval) in self.alpha_cut_series(alphas, domain) {
+            if let Some((left, right)) = interval {
+                let _ = writeln!(
+                    svg,
+                    "  <line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"black\" />",
+                    scale_x(left),
+                    scale_y(alpha),
+                    scale_x(right),
+                    scale_y(alpha)
+                );
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    pub fn threshold_to_crisp(&self, alpha: f64, domain: &Domain) -> Vec<f64> {
+        self.alpha_cut(alpha, domain)
+    }
+
+    pub fn distance_to_nearest_crisp(&self, domain: &Domain) -> f64 {
+        domain
+            .points()
+            .map(|x| {
+                let mu = self.membership_degree(x);
+                let crisp = if mu >= 0.5 { 1.0 } else { 0.0 };
+                (mu - crisp).abs() * domain.step()
+            })
+            .sum()
+    }
+
+    pub fn bounding_box(&self, domain: &Domain) -> Option<(f64, f64)> {
+        let left = domain.points().find(|&x| self.membership_degree(x) > 0.0)?;
+        let right = domain.points().rfind(|&x| self.membership_degree(x) > 0.0)?;
+
+        Some((left, right))
+    }
+
+    pub fn is_bounded(&self, min_val: f64, max_val: f64, domain: &Domain) -> bool {
+        domain
+            .points()
+            .filter(|x| !(min_val..=max_val).contains(x))
+            .all(|x| self.membership_degree(x) == 0.0)
+    }
+
+    pub fn tight_bounds(&self, domain: &Domain, tol: f64) -> Option<(f64, f64)> {
+        self.alpha_cut_interval(tol, domain)
+    }
+
+    pub fn diameter(&self, domain: &Domain) -> f64 {
+        self.bounding_box(domain)
+            .map_or(0.0, |(left, right)| right - left)
+    }
+
+    fn cut_runs(&self, alpha: f64, domain: &Domain) -> Vec<(f64, f64)> {
+        let level = f64::max(alpha, 0.0);
+        let inside = |mu: f64| if alpha > 0.0 { mu >= alpha - MEMBERSHIP_TOLERANCE } else { mu > 0.0 };
+        let crossing = |(x_out, mu_out): (f64, f64), (x_in, mu_in): (f64, f64)| {
+            x_out + ((level - mu_out) / (mu_in - mu_out)).clamp(0.0, 1.0) * (x_in - x_out)
+        };
+
+        let samples = self.sample(domain);
+        let mut runs = Vec::new();
+        let mut start = None;
+
+        for (i, &(x, mu)) in samples.iter().enumerate() {
+            match (start, inside(mu)) {
+                (None, true) => start = Some(if i == 0 { x } else { crossing(samples[i - 1], samples[i]) }),
+                (Some(left), false) => {
+                    runs.push((left, crossing(samples[i], samples[i - 1])));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        if let (Some(left), Some(&(right, _))) = (start, samples.last()) {
+            runs.push((left, right));
+        }
+
+        runs
+    }
+
+    fn cut_width(&self, alpha: f64, domain: &Domain) -> f64 {
+        let runs = self.cut_runs(alpha, domain);
+
+        match (runs.first(), runs.last()) {
+            (Some(&(left, _)), Some(&(_, right))) => right - left,
+            _ => 0.0,
+        }
+    }
+
+    pub fn support_width(&self, domain: &Domain) -> f64 {
+        self.cut_width(0.0, domain)
+    }
+
+    pub fn area_above_alpha(&self, alpha: f64, domain: &Domain) -> f64 {
+        self.cut_runs(alpha, domain)
+            .into_iter()
+            .map(|(left, right)| right - left)
+            .sum()
+    }
+
+    pub fn pwhm(&self, domain: &Domain) -> f64 {
+        self.full_width_at_fraction_maximum(0.5, domain)
+    }
+
+    pub fn full_width_at_fraction_maximum(&self, fraction: f64, domain: &Domain) -> f64 {
+        if fraction <= 0.0 {
+            return self.support_width(domain);
+        }
+
+        let height = self.height(domain);
+
+        if height <= 0.0 {
+            return 0.0;
+        }
+
+        self.cut_width(fraction * height, domain)
+    }
+
+    pub fn partial_integral(&self, a: f64, b: f64, step: f64) -> f64 {
+        if a == b || step <= 0.0 {
+            return 0.0;
+        }
+
+        let intervals = f64::max(1.0, ((b - a).abs() / step).ceil()) as usize;
+        let h = (b - a) / intervals as f64;
+        let interior: f64 = (1..intervals)
+            .map(|i| self.membership_degree(a + i as f64 * h))
+            .sum();
+
+        h * ((self.membership_degree(a) + self.membership_degree(b)) / 2.0 + interior)
+    }
+
+    pub fn integrate(&self, domain: &Domain) -> f64 {
+        self.partial_integral(domain.min(), domain.max(), domain.step())
+    }
+
+    pub fn cumulative_distribution(&self, domain: &Domain) -> Vec<(f64, f64)> {
+        let samples = self.sample(domain);
+        let mut running = 0.0;
+        let mut cumulative = Vec::with_capacity(samples.len());
+
+        for (i, &(x, mu)) in samples.iter().enumerate() {
+            if i > 0 {
+                let (prev_x, prev_mu) = samples[i - 1];
+                running += (x - prev_x) * (mu + prev_mu) / 2.0;
+            }
+            cumulative.push((x, running));
+        }
+
+        if running > 0.0 {
+            for point in &mut cumulative {
+                point.1 /= running;
+            }
+        }
+
+        cumulative
+    }
+
+    pub fn to_pdf(&self, domain: &Domain) -> FuzzySet {
+        let area = self.integrate(domain);
+        let self_func = Arc::clone(&self.membership_function);
+
+        FuzzySet::new(
+            &format!("Pdf({})", self.name),
+            Arc::new(move |x| if area > 0.0 { self_func(x) / area } else { 0.0 }),
+        )
+    }
+
+    pub fn to_cdf(&self, domain: &Domain) -> FuzzySet {
+        let cumulative = self.cumulative_distribution(domain);
+        let upper = match cumulative.last() {
+            Some(&(x, total)) if total > 0.0 => x,
+            _ => f64::INFINITY,
+        };
+
+        FuzzySet::new(
+            &format!("Cdf({})", self.name),
+            Arc::new(move |x| if x > upper { 1.0 } else { interpolate_points(&cumulative, x) }),
+        )
+    }
+
+    pub fn percentile(&self, p: f64, domain: &Domain) -> Result<f64, FuzzyError> {
+        if !(0.0..=1.0).contains(&p) {
+            return Err(FuzzyError::InvalidPercentile(p));
+        }
+
+        let cumulative = self.cumulative_distribution(domain);
+
+        if cumulative.last().is_none_or(|&(_, total)| total <= 0.0) {
+            return Err(FuzzyError::EmptySupport);
+        }
+
+        let index = cumulative.partition_point(|&(_, fraction)| fraction < p);
+
+        if index == 0 {
+            return Ok(cumulative[0].0);
+        }
+
+        let (x0, f0) = cumulative[index - 1];
+        let (x1, f1) = cumulative[usize::min(index, cumulative.len() - 1)];
+
+        if f1 == f0 {
+            Ok(x1)
+        } else {
+            Ok(x0 + (x1 - x0) * (p - f0) / (f1 - f0))
+        }
+    }
+
+    pub fn defuzzify_bisector(&self, domain: &Domain) -> Option<f64> {
+        self.percentile(0.5, domain).ok()
+    }
+
+    pub fn defuzzify_weighted_bisector(&self, weight_fn: &FuzzySet, domain: &Domain) -> Option<f64> {
+        self.product_unclamped(weight_fn).defuzzify_bisector(domain)
+    }
+
+    pub fn bandwidth(&self, domain: &Domain) -> f64 {
+        let crossover = self.alpha_cut(0.5, domain);
+
+        match (crossover.first(), crossover.last()) {
+            (Some(left), Some(right)) => right - left,
+            _ => 0.0,
+        }
+    }
+
+    pub fn iqr(&self, domain: &Domain) -> Result<f64, FuzzyError> {
+        Ok(self.percentile(0.75, domain)? - self.percentile(0.25, domain)?)
+    }
+
+    pub fn spread_ratio(&self, domain: &Domain) -> f64 {
+        let bandwidth = self.bandwidth(domain);
+
+        match self.iqr(domain) {
+            Ok(iqr) if bandwidth > 0.0 => iqr / bandwidth,
+            _ => 0.0,
+        }
+    }
+
+    pub fn specificity(&self, domain: &Domain) -> f64 {
+        1.0 - self.integrate(domain) / domain.width()
+    }
+
+    pub fn information_content_bits(&self, domain: &Domain) -> f64 {
+        -(1.0 - self.specificity(domain)).log2()
+    }
+
+    pub fn scalar_cardinality(&self, domain: &Domain) -> f64 {
+        domain.points()
+            .map(|x| self.membership_degree(x) * domain.step())
+            .sum()
+    }
+
+    pub fn volume(&self, domain: &Domain, alpha_step: f64) -> f64 {
+        if alpha_step <= 0.0 {
+            return 0.0;
+        }
+
+        let levels = (1.0 / alpha_step).ceil() as usize;
+
+        (0..levels)
+            .map(|k| {
+                let low = k as f64 * alpha_step;
+                let high = f64::min(1.0, low + alpha_step);
+                let alpha = (low + high) / 2.0;
+                self.area_above_alpha(alpha, domain) * (high - low)
+            })
+            .sum()
+    }
+
+    pub fn overlap_measure(&self, other: &FuzzySet, domain: &Domain) -> f64 {
+        let self_cardinality = self.scalar_cardinality(domain);
+        let other_cardinality = other.scalar_cardinality(domain);
+
+        if self_cardinality == 0.0 || other_cardinality == 0.0 {
+            return 0.0;
+        }
+
+        self.intersection(other).scalar_cardinality(domain)
+            / f64::min(self_cardinality, other_cardinality)
+    }
+
+    pub fn is_ruspini_partition(sets: &[&FuzzySet], domain: &Domain, tol: f64) -> bool {
+        domain.points().all(|x| {
+            let total: f64 = sets.iter().map(|set| set.membership_degree(x)).sum();
+            (total - 1.0).abs() <= tol
+        })
+    }
+
+    pub fn area_between(&self, other: &FuzzySet, domain: &Domain) -> f64 {
+        domain
+            .points()
+            .map(|x| (self.membership_degree(x) - other.membership_degree(x)) * domain.step())
+            .sum()
+    }
+
+    pub fn absolute_area_between(&self, other: &FuzzySet, domain: &Domain) -> f64 {
+        domain
+            .points()
+            .map(|x| (self.membership_degree(x) - other.membership_degree(x)).abs() * domain.step())
+            .sum()
+    }
+
+    pub fn dft_coefficients(&self, domain: &Domain, n_coeffs: usize) -> Vec<(f64, f64)> {
+        discrete_fourier_transform(&self.sample_membership_only(domain), n_coeffs)
+    }
+
+    pub fn dominant_frequency(&self, domain: &Domain) -> f64 {
+        let n = domain.sample_count();
+        let coefficients = self.dft_coefficients(domain, n / 2 + 1);
+
+        let dominant = coefficients
+            .iter()
+            .enumerate()
+            .skip(1)
+            .fold((0, 0.0), |best, (k, (re, im))| {
+                let magnitude = re.hypot(*im);
+                if magnitude > best.1 {
+                    (k, magnitude)
+                } else {
+                    best
+                }
+            })
+            .0;
+
+        dominant as f64 / (n as f64 * domain.step())
+    }
+
+    pub fn low_pass_filter(&self, domain: &Domain, cutoff_freq: f64) -> FuzzySet {
+        let samples = self.sample(domain);
+        let n = samples.len();
+        let memberships: Vec<f64> = samples.iter().map(|&(_, mu)| mu).collect();
+        let period = n as f64 * domain.step();
+
+        let coefficients: Vec<(f64, f64)> = discrete_fourier_transform(&memberships, n)
+            .into_iter()
+            .enumerate()
+            .map(|(k, coefficient)| {
+                if usize::min(k, n - k) as f64 / period > cutoff_freq {
+                    (0.0, 0.0)
+                } else {
+                    coefficient
+                }
+            })
+            .collect();
+
+        let points = samples
+            .iter()
+            .enumerate()
+            .map(|(j, &(x, _))| {
+                let value = coefficients.iter().enumerate().fold(0.0, |sum, (k, (re, im))| {
+                    let angle = 2.0 * std::f64::consts::PI * (k * j) as f64 / n as f64;
+                    sum + re * angle.cos() - im * angle.sin()
+                });
+                (x, (value / n as f64).clamp(0.0, 1.0))
+            })
+            .collect();
+
+        FuzzySet::piecewise_linear(&format!("LowPass({})", self.name), points)
+    }
+
+    pub fn autocorrelation(&self, domain: &Domain) -> Vec<(f64, f64)> {
+        self.correlation(self, domain)
+    }
+
+    pub fn correlation(&self, other: &FuzzySet, domain: &Domain) -> Vec<(f64, f64)> {
+        let a = self.sample_membership_only(domain);
+        let b = other.sample_membership_only(domain);
+
+        (0..a.len())
+            .map(|lag| {
+                let sum: f64 = a.iter().zip(&b[lag..]).map(|(mu_a, mu_b)| mu_a * mu_b).sum();
+                (lag as f64 * domain.step(), sum * domain.step())
+            })
+            .collect()
+    }
+
+    pub fn l_infinity_norm(&self, domain: &Domain) -> f64 {
+        self.height(domain)
+    }
+
+    pub fn l1_norm(&self, domain: &Domain) -> f64 {
+        self.integrate(domain)
+    }
+
+    pub fn l2_norm(&self, domain: &Domain) -> f64 {
+        self.transform_codomain(|mu| mu * mu).integrate(domain).sqrt()
+    }
+
+    pub fn l1_distance(&self, other: &FuzzySet, domain: &Domain) -> f64 {
+        self.compose(other, |a, b| (a - b).abs(), "L1").integrate(domain)
+    }
+
+    pub fn l2_distance(&self, other: &FuzzySet, domain: &Domain) -> f64 {
+        self.compose(other, |a, b| (a - b).powi(2), "L2")
+            .integrate(domain)
+            .sqrt()
+    }
+
+    pub fn membership_equal_robust(&self, other: &FuzzySet, domain: &Domain) -> bool {
+        domain
+            .points()
+            .all(|x| (self.membership_degree(x) - other.membership_degree(x)).abs() < f64::EPSILON * 16.0)
+    }
+
+    pub fn jaccard_similarity(&self, other: &FuzzySet, domain: &Domain) -> f64 {
+        let union_cardinality = self.union(other).scalar_cardinality(domain);
+
+        if union_cardinality == 0.0 {
+            return 0.0;
+        }
+
+        self.intersection(other).scalar_cardinality(domain) / union_cardinality
+    }
+
+    pub fn dice_similarity(&self, other: &FuzzySet, domain: &Domain) -> f64 {
+        let total_cardinality = self.scalar_cardinality(domain)
+            + other.scalar_cardinality(domain);
+
+        if total_cardinality == 0.0 {
+            return 0.0;
+        }
+
+        2.0 * self.intersection(other).scalar_cardinality(domain) / total_cardinality
+    }
+}
+
+impl PartialEq for FuzzySet {
+    fn eq(&self, other: &FuzzySet) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for FuzzySet {
+    fn partial_cmp(&self, other: &FuzzySet) -> Option<Ordering> {
+        let domain = Domain::default();
+
+        self.centroid(&domain).partial_cmp(&other.centroid(&domain))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CrispSet {
+    intervals: Vec<(f64, f64)>,
+}
+
+impl CrispSet {
+    pub fn new(intervals: Vec<(f64, f64)>) -> Self {
+        CrispSet { intervals }
+    }
+
+    pub fn interval(min: f64, max: f64) -> Self {
+        CrispSet::new(vec![(min, max)])
+    }
+
+    pub fn intervals(&self) -> &[(f64, f64)] {
+        &self.intervals
+    }
+
+    pub fn contains(&self, x: f64) -> bool {
+        self.intervals.iter().any(|&(min, max)| x >= min && x <= max)
+    }
+}
+
+#[derive(Clone)]
+pub struct PossibilityDistribution(FuzzySet);
+
+impl PossibilityDistribution {
+    pub fn new(set: FuzzySet, domain: &Domain) -> Result<Self, FuzzyError> {
+        if !set.is_normal(domain) {
+            return Err(FuzzyError::NotNormal {
+                height: set.height(domain),
+            });
+        }
+
+        Ok(PossibilityDistribution(set))
+    }
+
+    pub fn as_fuzzy_set(&self) -> &FuzzySet {
+        &self.0
+    }
+
+    pub fn into_fuzzy_set(self) -> FuzzySet {
+        self.0
+    }
+
+    pub fn possibility(&self, x: f64) -> f64 {
+        self.0.membership_degree(x)
+    }
+
+    pub fn necessity(&self, event: &CrispSet, domain: &Domain) -> f64 {
+        let outside = domain
+            .points()
+            .filter(|&x| !event.contains(x))
+            .map(|x| self.possibility(x))
+            .fold(0.0, f64::max);
+
+        1.0 - outside
+    }
+
+    pub fn consistency_with(&self, other: &PossibilityDistribution, domain: &Domain) -> f64 {
+        domain
+            .points()
+            .map(|x| f64::min(self.possibility(x), other.possibility(x)))
+            .fold(0.0, f64::max)
+    }
+}
+
+#[derive(Clone)]
+enum Condition {
+    Crisp(Arc<dyn Fn(f64) -> bool + Send + Sync>),
+    Graded(Arc<dyn Fn(f64) -> f64 + Send + Sync>),
+}
+
+#[derive(Clone)]
+pub struct FuzzyRelation {
+    name: String,
+    relation: Arc<dyn Fn(f64, f64) -> f64 + Send + Sync>,
+}
+
+impl FuzzyRelation {
+    pub fn new(name: &str, relation: Arc<dyn Fn(f64, f64) -> f64 + Send + Sync>) -> Self {
+        FuzzyRelation {
+            name: name.to_string(),
+            relation,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn degree(&self, x: f64, y: f64) -> f64 {
+        (self.relation)(x, y)
+    }
+
+    pub fn compose_max_min(&self, input: &FuzzySet, x_domain: &Domain) -> FuzzySet {
+        let samples = input.sample(x_domain);
+        let relation = Arc::clone(&self.relation);
+
+        FuzzySet::new(
+            &format!("Composition({}, {})", input.name, self.name),
+            Arc::new(move |y| {
+                samples
+                    .iter()
+                    .map(|&(x, mu)| f64::min(mu, relation(x, y)))
+                    .fold(0.0, f64::max)
+            }),
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct Type2FuzzySet {
+    name: String,
+    lower_mf: FuzzySet,
+    upper_mf: FuzzySet,
+}
+
+impl Type2FuzzySet {
+    pub fn new(name: &str, lower: FuzzySet, upper: FuzzySet, domain: &Domain) -> Result<Self, FuzzyError> {
+        if let Some(x) = domain
+            .points()
+            .find(|&x| lower.membership_degree(x) > upper.membership_degree(x) + MEMBERSHIP_TOLERANCE)
+        {
+            return Err(FuzzyError::InvalidFootprint { x });
+        }
+
+        Ok(Type2FuzzySet {
+            name: name.to_string(),
+            lower_mf: lower,
+            upper_mf: upper,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn lower(&self) -> &FuzzySet {
+        &self.lower_mf
+    }
+
+    pub fn upper(&self) -> &FuzzySet {
+        &self.upper_mf
+    }
+
+    pub fn centroid_type_reduction(&self, domain: &Domain) -> (f64, f64) {
+        let samples: Vec<(f64, f64, f64)> = domain
+            .points()
+            .map(|x| (x, self.lower_mf.membership_degree(x), self.upper_mf.membership_degree(x)))
+            .collect();
+
+        (
+            karnik_mendel(&samples, true),
+            karnik_mendel(&samples, false),
+        )
+    }
+}
+
+fn karnik_mendel(samples: &[(f64, f64, f64)], left: bool) -> f64 {
+    const MAX_ITERATIONS: usize = 100;
+
+    let weighted_mean = |switch: f64| {
+        let (numerator, denominator) = samples.iter().fold((0.0, 0.0), |(num, den), &(x, lower, upper)| {
+            let mu = if (x <= switch) == left { upper } else { lower };
+            (num + x * mu, den + mu)
+        });
+
+        if denominator == 0.0 {
+            0.0
+        } else {
+            numerator / denominator
+        }
+    };
+
+    let (numerator, denominator) = samples
+        .iter()
+        .fold((0.0, 0.0), |(num, den), &(x, lower, upper)| {
+            let mu = (lower + upper) / 2.0;
+            (num + x * mu, den + mu)
+        });
+
+    if denominator == 0.0 {
+        return 0.0;
+    }
+
+    let mut centroid = numerator / denominator;
+
+    for _ in 0..MAX_ITERATIONS {
+        let next = weighted_mean(centroid);
+
+        if (next - centroid).abs() <= MEMBERSHIP_TOLERANCE {
+            return next;
+        }
+
+        centroid = next;
+    }
+
+    centroid
+}
+
+#[derive(Clone)]
+pub struct LinguisticVariable {
+    name: String,
+    terms: Vec<FuzzySet>,
+}
+
+impl LinguisticVariable {
+    pub fn new(name: &str, terms: Vec<FuzzySet>) -> Self {
+        LinguisticVariable {
+            name: name.to_string(),
+            terms,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn terms(&self) -> &[FuzzySet] {
+        &self.terms
+    }
+
+    pub fn term(&self, name: &str) -> Option<&FuzzySet> {
+        self.terms.iter().find(|term| term.name == name)
+    }
+
+    pub fn fuzzify_all(&self, x: f64) -> HashMap<String, f64> {
+        self.terms
+            .iter()
+            .map(|term| (term.name.clone(), term.fuzzify(x)))
+            .collect()
+    }
+
+    pub fn best_term(&self, x: f64) -> Option<&FuzzySet> {
+        self.terms
+            .iter()
+            .map(|term| (term, term.membership_degree(x)))
+            .filter(|&(_, mu)| mu > 0.0)
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+            .map(|(term, _)| term)
+    }
+}
+
+#[derive(Clone)]
+pub struct FuzzyRule {
+    name: Option<String>,
+    condition: Condition,
+    consequence: FuzzySet,
+    weight: f64,
+    confidence: f64,
+}
+
+impl FuzzyRule {
+    pub fn new(
+        condition: Box<dyn Fn(f64) -> bool + Send + Sync>,
+        consequence: FuzzySet,
+        weight: f64,
+    ) -> Self {
+        FuzzyRule {
+            name: None,
+            condition: Condition::Crisp(Arc::from(condition)),
+            consequence,
+            weight,
+            confidence: 1.0,
+        }
+    }
+
+    pub fn new_graded(
+        condition: Box<dyn Fn(f64) -> f64 + Send + Sync>,
+        consequence: FuzzySet,
+        weight: f64,
+    ) -> Self {
+        FuzzyRule {
+            name: None,
+            condition: Condition::Graded(Arc::from(condition)),
+            consequence,
+            weight,
+            confidence: 1.0,
+        }
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn with_confidence(mut self, confidence: f64) -> Result<Self, FuzzyError> {
+        if !(0.0..=1.0).contains(&confidence) {
+            return Err(FuzzyError::InvalidConfidence(confidence));
+        }
+
+        self.confidence = confidence;
+        Ok(self)
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    pub fn confidence(&self) -> f64 {
+        self.confidence
+    }
+
+    pub fn firing_strength(&self, input: f64) -> f64 {
+        match &self.condition {
+            Condition::Crisp(condition) => {
+                if condition(input) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Condition::Graded(condition) => condition(input),
+        }
+    }
+
+    pub fn evaluate(&self, input: f64) -> Option<(FuzzySet, f64)> {
+        let strength = self.firing_strength(input);
+
+        if strength > 0.0 {
+            Some((self.consequence.clone(), self.weight * self.confidence * strength))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ConditionRegistry {
+    conditions: HashMap<String, Condition>,
+    consequences: HashMap<String, FuzzySet>,
+}
+
+impl ConditionRegistry {
+    pub fn new() -> Self {
+        ConditionRegistry::default()
+    }
+
+    pub fn register(&mut self, name: &str, condition: Arc<dyn Fn(f64) -> bool + Send + Sync>) {
+        self.conditions
+            .insert(name.to_string(), Condition::Crisp(condition));
+    }
+
+    pub fn register_graded(&mut self, name: &str, condition: Arc<dyn Fn(f64) -> f64 + Send + Sync>) {
+        self.conditions
+            .insert(name.to_string(), Condition::Graded(condition));
+    }
+
+    pub fn register_consequence(&mut self, consequence: FuzzySet) {
+        self.consequences.insert(consequence.name.clone(), consequence);
+    }
+
+    pub fn build_rule(&self, name: &str, consequence: &str, weight: f64) -> Result<FuzzyRule, FuzzyError> {
+        let consequence = self
+            .consequences
+            .get(consequence)
+            .cloned()
+            .ok_or_else(|| FuzzyError::UnknownConsequence(consequence.to_string()))?;
+
+        let condition = self
+            .conditions
+            .get(name)
+            .cloned()
+            .ok_or_else(|| FuzzyError::UnknownCondition(name.to_string()))?;
+
+        Ok(FuzzyRule {
+            name: Some(name.to_string()),
+            condition,
+            consequence,
+            weight,
+            confidence: 1.0,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct FiredRule {
+    pub index: usize,
+    pub name: Option<String>,
+    pub weight: f64,
+    pub consequence: String,
+    pub contribution: f64,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct InferenceTrace {
+    pub input: f64,
+    pub fired_rules: Vec<FiredRule>,
+    pub weighted_sum: f64,
+    pub total_weight: f64,
+    #[cfg_attr(feature = "serde", serde(rename = "raw_score"))]
+    pub score: f64,
+    #[cfg_attr(feature = "serde", serde(rename = "numeric_score"))]
+    pub output_score: f64,
+    pub output: String,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct TraceRecord<'a> {
+    #[serde(flatten)]
+    trace: &'a InferenceTrace,
+    weights: HashMap<String, f64>,
+}
+
+#[cfg(feature = "serde")]
+impl InferenceTrace {
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        let weights = self
+            .fired_rules
+            .iter()
+            .map(|rule| {
+                let label = rule.name.clone().unwrap_or_else(|| rule.index.to_string());
+                (label, rule.weight)
+            })
+            .collect();
+
+        serde_json::to_string(&TraceRecord { trace: self, weights })
+    }
+}
+
+impl fmt::Display for InferenceTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Input: {}", self.input)?;
+
+        if self.fired_rules.is_empty() {
+            writeln!(f, "No rules fired")?;
+        }
+
+        for rule in &self.fired_rules {
+            match &rule.name {
+                Some(name) => write!(f, "Rule {}", name)?,
+                None => write!(f, "Rule {}", rule.index)?,
+            }
+            writeln!(
+                f,
+                ": FIRED with weight {:.2}, consequence {}, contributing {:.2} to score",
+                rule.weight, rule.consequence, rule.contribution
+            )?;
+        }
+
+        writeln!(
+            f,
+            "Score: {:.2} / {:.2} = {:.2}",
+            self.weighted_sum, self.total_weight, self.score
+        )?;
+        if self.output_score == self.score {
+            write!(f, "Output: {} (score {:.2})", self.output, self.score)
+        } else {
+            write!(
+                f,
+                "Output: {} (score {:.2}, transformed to {:.2})",
+                self.output, self.score, self.output_score
+            )
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct RuleRecord {
+    name: String,
+    weight: f64,
+    #[serde(default = "full_confidence")]
+    confidence: f64,
+    consequence: String,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct RuleBaseRecord {
+    rules: Vec<RuleRecord>,
+}
+
+#[cfg(feature = "serde")]
+fn full_confidence() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputValidationMode {
+    Clamp,
+    Reject,
+    Warn,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InferenceMode {
+    WeightedAggregate,
+    FirstMatch,
+    LastMatch,
+    BestMatch,
+}
+
+#[derive(Clone)]
+pub struct InferenceEngine {
+    rules: Vec<FuzzyRule>,
+    default_output: String,
+    default_numeric: f64,
+    input_range: Option<(f64, f64)>,
+    validation_mode: InputValidationMode,
+    warning_callback: Option<Arc<dyn Fn(f64) + Send + Sync>>,
+    output_transformer: Option<Arc<dyn Fn(f64) -> f64 + Send + Sync>>,
+    inference_mode: InferenceMode,
+}
+
+impl InferenceEngine {
+    pub fn new(rules: Vec<FuzzyRule>) -> Self {
+        InferenceEngine {
+            rules,
+            default_output: "Low Priority".to_string(),
+            default_numeric: 0.0,
+            input_range: None,
+            validation_mode: InputValidationMode::Clamp,
+            warning_callback: None,
+            output_transformer: None,
+            inference_mode: InferenceMode::WeightedAggregate,
+        }
+    }
+
+    pub fn with_input_validation(mut self, range: (f64, f64), mode: InputValidationMode) -> Self {
+        self.input_range = Some(range);
+        self.validation_mode = mode;
+        self
+    }
+
+    pub fn with_warning_callback(mut self, callback: Arc<dyn Fn(f64) + Send + Sync>) -> Self {
+        self.warning_callback = Some(callback);
+        self
+    }
+
+    pub fn with_output_transformer(mut self, f: Arc<dyn Fn(f64) -> f64 + Send + Sync>) -> Self {
+        self.output_transformer = Some(f);
+        self
+    }
+
+    pub fn with_inference_mode(mut self, mode: InferenceMode) -> Self {
+        self.inference_mode = mode;
+        self
+    }
+
+    pub fn with_default_output(mut self, default: String) -> Self {
+        self.default_output = default;
+        self
+    }
+
+    pub fn with_default_numeric(mut self, default: f64) -> Self {
+        self.default_numeric = default;
+        self
+    }
+
+    pub fn add_rule(&mut self, rule: FuzzyRule) {
+        self.rules.push(rule);
+    }
+
+    pub fn add_rules_batch(&mut self, rules: Vec<FuzzyRule>) {
+        self.rules.extend(rules);
+    }
+
+    pub fn merge(&self, other: &InferenceEngine) -> InferenceEngine {
+        let mut merged = self.clone();
+
+        for rule in &other.rules {
+            let mut rule = rule.clone();
+
+            if let Some(name) = rule.name.clone() {
+                let taken = |candidate: &str| {
+                    merged
+                        .rules
+                        .iter()
+                        .any(|existing| existing.name.as_deref() == Some(candidate))
+                };
+
+                if taken(&name) {
+                    let suffix = (2..).find(|n| !taken(&format!("{} ({})", name, n))).unwrap_or(2);
+                    rule.name = Some(format!("{} ({})", name, suffix));
+                }
+            }
+
+            merged.rules.push(rule);
+        }
+
+        merged
+    }
+
+    pub fn subtract_rules(&self, other: &InferenceEngine) -> InferenceEngine {
+        let mut remaining = self.clone();
+
+        remaining.rules.retain(|rule| match &rule.name {
+            Some(name) => !other.rules.iter().any(|removed| removed.name.as_ref() == Some(name)),
+            None => true,
+        });
+
+        remaining
+    }
+
+    pub fn add_rules_from_truth_table(
+        &mut self,
+        input_var: &LinguisticVariable,
+        output_sets: &[&FuzzySet],
+        table: &[&str],
+    ) -> Result<(), FuzzyError> {
+        if table.len() != input_var.terms.len() {
+            return Err(FuzzyError::TruthTableSize {
+                expected: input_var.terms.len(),
+                actual: table.len(),
+            });
+        }
+
+        let mut rules = Vec::with_capacity(table.len());
+
+        for (term, &label) in input_var.terms.iter().zip(table) {
+            let output = output_sets
+                .iter()
+                .find(|set| set.name == label)
+                .ok_or_else(|| FuzzyError::UnknownConsequence(label.to_string()))?;
+            let condition = term.clone();
+
+            rules.push(
+                FuzzyRule::new_graded(
+                    Box::new(move |x| condition.membership_degree(x)),
+                    (*output).clone(),
+                    1.0,
+                )
+                .with_name(&format!("{} is {} -> {}", input_var.name, term.name, label)),
+            );
+        }
+
+        self.rules.extend(rules);
+        Ok(())
+    }
+
+    pub fn get_rule_weights(&self) -> Vec<f64> {
+        self.rules.iter().map(|rule| rule.weight).collect()
+    }
+
+    pub fn set_rule_weights(&mut self, weights: &[f64]) -> Result<(), FuzzyError> {
+        self.reset_weights(weights)
+    }
+
+    pub fn normalize_weights(&mut self) {
+        let total: f64 = self.rules.iter().map(|rule| rule.weight).sum();
+
+        if total > 0.0 {
+            for rule in &mut self.rules {
+                rule.weight /= total;
+            }
+        }
+    }
+
+    pub fn rule_count(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn reset_weights_to_uniform(&mut self) {
+        let weight = 1.0 / self.rule_count() as f64;
+
+        for rule in &mut self.rules {
+            rule.weight = weight;
+        }
+    }
+
+    pub fn reset_weights(&mut self, weights: &[f64]) -> Result<(), FuzzyError> {
+        if weights.len() != self.rules.len() {
+            return Err(FuzzyError::WeightCountMismatch {
+                expected: self.rules.len(),
+                actual: weights.len(),
+            });
+        }
+
+        for &weight in weights {
+            check_weight(weight)?;
+        }
+
+        let total: f64 = weights.iter().sum();
+
+        if !weights.is_empty() && total <= 0.0 {
+            return Err(FuzzyError::NonPositiveTotalWeight(total));
+        }
+
+        for (rule, &weight) in self.rules.iter_mut().zip(weights) {
+            rule.weight = weight;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn serialize_rules_json(&self) -> Result<String, serde_json::Error> {
+        use serde::ser::Error;
+
+        let rules = self
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                let name = rule
+                    .name
+                    .clone()
+                    .ok_or_else(|| serde_json::Error::custom(FuzzyError::UnnamedRule(index)))?;
+
+                check_weight(rule.weight).map_err(serde_json::Error::custom)?;
+
+                Ok(RuleRecord {
+                    name,
+                    weight: rule.weight,
+                    confidence: rule.confidence,
+                    consequence: rule.consequence.name.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, serde_json::Error>>()?;
+
+        serde_json::to_string(&RuleBaseRecord { rules })
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn deserialize_rules_json(
+        json: &str,
+        condition_registry: &ConditionRegistry,
+    ) -> Result<InferenceEngine, FuzzyError> {
+        let record: RuleBaseRecord =
+            serde_json::from_str(json).map_err(|error| FuzzyError::Deserialization(error.to_string()))?;
+
+        let mut rules = Vec::with_capacity(record.rules.len());
+
+        for entry in record.rules {
+            check_weight(entry.weight)?;
+            rules.push(
+                condition_registry
+                    .build_rule(&entry.name, &entry.consequence, entry.weight)?
+                    .with_confidence(entry.confidence)?,
+            );
+        }
+
+        Ok(InferenceEngine::new(rules))
+    }
+
+    pub fn from_csv_training_data(
+        csv: &str,
+        input_var: &LinguisticVariable,
+        output_sets: &[&FuzzySet],
+    ) -> Result<InferenceEngine, FuzzyError> {
+        let mut counts: Vec<(&FuzzySet, &FuzzySet, usize)> = Vec::new();
+        let mut total = 0;
+        let mut first_row = true;
+
+        for (index, line) in csv.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let invalid = |message: &str| FuzzyError::InvalidCsv {
+                line: index + 1,
+                message: message.to_string(),
+            };
+
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+
+            if fields.len() != 2 {
+                return Err(invalid("expected an input value and an output label"));
+            }
+
+            let is_header = first_row;
+            first_row = false;
+
+            let input: f64 = match fields[0].parse() {
+                Ok(input) => input,
+                Err(_) if is_header => continue,
+                Err(_) => return Err(invalid("input is not a number")),
+            };
+
+            let output = output_sets
+                .iter()
+                .find(|set| set.name == fields[1])
+                .ok_or_else(|| FuzzyError::UnknownConsequence(fields[1].to_string()))?;
+
+            let term = match input_var.best_term(input) {
+                Some(term) => term,
+                None => continue,
+            };
+
+            total += 1;
+
+            match counts
+                .iter_mut()
+                .find(|(t, o, _)| t.name == term.name && o.name == output.name)
+            {
+                Some((_, _, count)) => *count += 1,
+                None => counts.push((term, output, 1)),
+            }
+        }
+
+        let rules = counts
+            .into_iter()
+            .map(|(term, output, count)| {
+                let condition = term.clone();
+
+                FuzzyRule::new_graded(
+                    Box::new(move |x| condition.membership_degree(x)),
+                    output.clone(),
+                    count as f64 / total as f64,
+                )
+                .with_name(&format!("{} is {} -> {}", input_var.name, term.name, output.name))
+            })
+            .collect();
+
+        Ok(InferenceEngine::new(rules))
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<FuzzyError>> {
+        let mut errors = Vec::new();
+
+        if self.rules.is_empty() {
+            errors.push(FuzzyError::NoRules);
+        }
+
+        for rule in &self.rules {
+            if let Err(error) = check_weight(rule.weight) {
+                errors.push(error);
+            }
+
+            if let Some(height) = rule.consequence.shape_height() {
+                if height.partial_cmp(&0.0) != Some(Ordering::Greater) {
+                    errors.push(FuzzyError::EmptySupport);
+                } else if height < 1.0 - MEMBERSHIP_TOLERANCE {
+                    errors.push(FuzzyError::NotNormal { height });
+                }
+            }
+        }
+
+        let total: f64 = self.rules.iter().map(|rule| rule.weight).sum();
+
+        if !self.rules.is_empty() && total.partial_cmp(&0.0) != Some(Ordering::Greater) {
+            errors.push(FuzzyError::NonPositiveTotalWeight(total));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn infer(&self, input: f64) -> String {
+        self.try_infer(input)
+            .unwrap_or_else(|_| self.default_output.clone())
+    }
+
+    pub fn try_infer(&self, input: f64) -> Result<String, FuzzyError> {
+        self.check_rule_base()?;
+        let results = self.evaluate_rules(self.validate_input(input)?);
+
+        Ok(self.aggregate_results(&results))
+    }
+
+    pub fn infer_numeric(&self, input: f64) -> f64 {
+        self.try_infer_numeric(input).unwrap_or(self.default_numeric)
+    }
+
+    pub fn try_infer_numeric(&self, input: f64) -> Result<f64, FuzzyError> {
+        self.check_rule_base()?;
+        let results = self.evaluate_rules(self.validate_input(input)?);
+
+        Ok(self
+            .weighted_score(&results)
+            .map(|score| self.transform_output(score))
+            .unwrap_or(self.default_numeric))
+    }
+
+    pub fn output_distribution(&self, input: f64) -> HashMap<String, f64> {
+        let results = match self.validate_input(input) {
+            Ok(input) => self.evaluate_rules(input),
+            Err(_) => return HashMap::new(),
+        };
+
+        let total_weight: f64 = results.iter().map(|(_, weight)| weight).sum();
+        let mut distribution = HashMap::new();
+
+        if total_weight <= 0.0 {
+            return distribution;
+        }
+
+        for (set, weight) in results {
+            *distribution.entry(set.name).or_insert(0.0) += weight / total_weight;
+        }
+
+        distribution
+    }
+
+    pub fn infer_with_confidence(&self, input: f64) -> (String, f64) {
+        let label = self.infer(input);
+        let confidence = self
+            .output_distribution(input)
+            .get(&label)
+            .copied()
+            .unwrap_or(0.0);
+
+        (label, confidence)
+    }
+
+    pub fn infer_streaming<I: IntoIterator<Item = f64>>(
+        &self,
+        inputs: I,
+        confidence_threshold: f64,
+    ) -> Option<(String, f64)> {
+        self.infer_all_streaming(inputs)
+            .find(|(_, confidence)| *confidence > confidence_threshold)
+    }
+
+    pub fn infer_all_streaming<'a, I: IntoIterator<Item = f64> + 'a>(
+        &'a self,
+        inputs: I,
+    ) -> impl Iterator<Item = (String, f64)> + 'a {
+        inputs
+            .into_iter()
+            .map(move |input| self.infer_with_confidence(input))
+    }
+
+    pub fn consequent_fuzzy_sets(&self, input: f64) -> Vec<(FuzzySet, f64)> {
+        match self.validate_input(input) {
+            Ok(input) => self.evaluate_rules(input),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    pub fn aggregate_weighted_fuzzy_sets(sets: &[(FuzzySet, f64)]) -> FuzzySet {
+        let weighted: Vec<(&FuzzySet, f64)> = sets.iter().map(|(set, weight)| (set, *weight)).collect();
+
+        FuzzySet::aggregate_with_weights("Output", &weighted, ConormStrategy::Max)
+    }
+
+    pub fn output_fuzzy_set(&self, input: f64) -> FuzzySet {
+        let results = self.consequent_fuzzy_sets(input);
+
+        let clipped: Vec<FuzzySet> = results
+            .iter()
+            .map(|(set, weight)| set.clip(*weight))
+            .collect();
+
+        FuzzySet::union_all("Output", &clipped.iter().collect::<Vec<_>>())
+    }
+
+    pub fn input_sensitivity_fuzzy(&self, base_input: f64, delta: f64, domain: &Domain) -> f64 {
+        self.output_fuzzy_set(base_input)
+            .absolute_area_between(&self.output_fuzzy_set(base_input + delta), domain)
+    }
+
+    pub fn probabilistic_infer(
+        &self,
+        input: f64,
+        uniform: &mut impl FnMut() -> f64,
+        n_samples: usize,
+    ) -> HashMap<String, f64> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+
+        let input = match self.validate_input(input) {
+            Ok(input) => input,
+            Err(_) => return HashMap::new(),
+        };
+
+        for _ in 0..n_samples {
+            let results: Vec<(FuzzySet, f64)> = self
+                .rules
+                .iter()
+                .filter(|rule| uniform() < rule.firing_strength(input))
+                .map(|rule| (rule.consequence.clone(), rule.weight * rule.confidence))
+                .collect();
+
+            *counts.entry(self.aggregate_results(&results)).or_insert(0) += 1;
+        }
+
+        counts
+            .into_iter()
+            .map(|(label, count)| (label, count as f64 / n_samples as f64))
+            .collect()
+    }
+
+    pub fn clone_with_perturbed_weights(&self, uniform: &mut impl FnMut() -> f64, sigma: f64) -> InferenceEngine {
+        let mut engine = self.clone();
+
+        for rule in &mut engine.rules {
+            rule.weight = (rule.weight + sigma * standard_normal(uniform)).clamp(0.0, 1.0);
+        }
+
+        engine
+    }
+
+    pub fn monte_carlo_infer(
+        &self,
+        uniform: &mut impl FnMut() -> f64,
+        input: f64,
+        n: usize,
+        sigma: f64,
+    ) -> (String, f64) {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        let mut scores = Vec::with_capacity(n);
+
+        for _ in 0..n {
+            let engine = self.clone_with_perturbed_weights(uniform, sigma);
+
+            if engine.validate().is_err() {
+                continue;
+            }
+
+            if let (Ok(label), Ok(score)) = (engine.try_infer(input), engine.try_infer_numeric(input)) {
+                *counts.entry(label).or_insert(0) += 1;
+                scores.push(score);
+            }
+        }
+
+        let label = counts
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+            .map_or_else(|| self.default_output.clone(), |(label, _)| label);
+
+        if scores.is_empty() {
+            return (label, 0.0);
+        }
+
+        scores.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let last = scores.len() - 1;
+        let lower = scores[(0.025 * last as f64).round() as usize];
+        let upper = scores[(0.975 * last as f64).round() as usize];
+
+        (label, upper - lower)
+    }
+
+    pub fn soft_threshold_infer(&self, input: f64, threshold: f64) -> String {
+        let input = match self.validate_input(input) {
+            Ok(input) => input,
+            Err(_) => return self.default_output.clone(),
+        };
+
+        let results: Vec<(FuzzySet, f64)> = self
+            .rules
+            .iter()
+            .filter_map(|rule| {
+                let degree = rule.firing_strength(input);
+
+                if degree > threshold {
+                    let weight =
+                        rule.weight * rule.confidence * (degree - threshold) / (1.0 - threshold);
+                    Some((rule.consequence.clone(), weight))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        self.aggregate_results(&results)
+    }
+
+    pub fn tune_weights(&mut self, training_data: &[(f64, f64)], learning_rate: f64, epochs: usize) -> Vec<f64> {
+        let mut losses = Vec::with_capacity(epochs);
+
+        for _ in 0..epochs {
+            let mut gradients = vec![0.0; self.rules.len()];
+            let mut squared_error = 0.0;
+
+            for &(input, target) in training_data {
+                let (output, sample_gradients) = self.output_gradients(input);
+                let error = output - target;
+                squared_error += error * error;
+
+                for (gradient, sample_gradient) in gradients.iter_mut().zip(sample_gradients) {
+                    *gradient += 2.0 * error * sample_gradient;
+                }
+            }
+
+            let count = training_data.len().max(1) as f64;
+            losses.push(squared_error / count);
+
+            for (rule, gradient) in self.rules.iter_mut().zip(gradients) {
+                rule.weight = f64::max(0.0, rule.weight - learning_rate * gradient / count);
+            }
+        }
+
+        losses
+    }
+
+    pub fn online_update(&mut self, input: f64, target: f64, learning_rate: f64) {
+        self.tune_weights(&[(input, target)], learning_rate, 1);
+    }
+
+    pub fn rule_weight_sensitivity(&self, input: f64, delta: f64) -> Vec<f64> {
+        let base = self.infer_numeric(input);
+
+        (0..self.rules.len())
+            .map(|index| {
+                let mut perturbed = self.clone();
+                perturbed.rules[index].weight += delta;
+                (perturbed.infer_numeric(input) - base).abs() / delta
+            })
+            .collect()
+    }
+
+    pub fn cross_validate(
+        &self,
+        data: &[(f64, f64)],
+        k_folds: usize,
+        learning_rate: f64,
+        epochs: usize,
+    ) -> Vec<f64> {
+        (0..k_folds)
+            .filter_map(|fold| {
+                let (test, train): (Vec<_>, Vec<_>) = data
+                    .iter()
+                    .enumerate()
+                    .partition(|(i, _)| i % k_folds == fold);
+
+                if test.is_empty() {
+                    return None;
+                }
+
+                let train: Vec<(f64, f64)> = train.into_iter().map(|(_, &sample)| sample).collect();
+                let mut engine = self.clone();
+                engine.tune_weights(&train, learning_rate, epochs);
+
+                let squared_error: f64 = test
+                    .iter()
+                    .map(|(_, &(input, target))| (engine.infer_numeric(input) - target).powi(2))
+                    .sum();
+
+                Some(squared_error / test.len() as f64)
+            })
+            .collect()
+    }
+
+    pub fn priority_histogram(&self, inputs: &[f64]) -> HashMap<String, usize> {
+        let mut histogram = HashMap::new();
+
+        for &input in inputs {
+            *histogram.entry(self.infer(input)).or_insert(0) += 1;
+        }
+
+        histogram
+    }
+
+    pub fn priority_distribution(&self, inputs: &[f64]) -> HashMap<String, f64> {
+        self.priority_histogram(inputs)
+            .into_iter()
+            .map(|(label, count)| (label, count as f64 / inputs.len() as f64))
+            .collect()
+    }
+
+    pub fn trace(&self, input: f64) -> InferenceTrace {
+        let fired: Vec<(usize, &FuzzyRule, (FuzzySet, f64))> = match self.validate_input(input) {
+            Ok(input) => self
+                .rules
+                .iter()
+                .enumerate()
+                .filter_map(|(index, rule)| rule.evaluate(input).map(|result| (index, rule, result)))
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        let results: Vec<(FuzzySet, f64)> = fired.iter().map(|(_, _, result)| result.clone()).collect();
+        let fired = &fired[self.selected_range(&results)];
+
+        let total_weight: f64 = fired.iter().map(|(_, _, (_, weight))| weight).sum();
+        let weighted_sum: f64 = fired
+            .iter()
+            .map(|(_, _, (set, weight))| self.priority_mapping(&set.name) * weight)
+            .sum();
+        let fired_rules = fired
+            .iter()
+            .map(|(index, rule, (set, weight))| FiredRule {
+                index: *index,
+                name: rule.name.clone(),
+                weight: *weight,
+                consequence: set.name.clone(),
+                contribution: if total_weight > 0.0 {
+                    self.priority_mapping(&set.name) * weight / total_weight
+                } else {
+                    0.0
+                },
+            })
+            .collect();
+
+        let raw_score = self.weighted_score(&results);
+        let score = raw_score.unwrap_or(self.default_numeric);
+        let output_score = raw_score
+            .map(|score| self.transform_output(score))
+            .unwrap_or(self.default_numeric);
+
+        InferenceTrace {
+            input,
+            fired_rules,
+            weighted_sum,
+            total_weight,
+            score,
+            output_score,
+            output: self.aggregate_results(&results),
+        }
+    }
+
+    pub fn explain(&self, input: f64) -> String {
+        self.trace(input).to_string()
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn explain_json(&self, input: f64) -> Result<String, serde_json::Error> {
+        self.trace(input).to_json()
+    }
+
+    pub fn to_dot_graph(&self, rule_inputs: &[&[&str]]) -> String {
+        use std::fmt::Write as _;
+
+        let mut inputs: Vec<&str> = Vec::new();
+        for &name in rule_inputs.iter().flat_map(|names| names.iter()) {
+            if !inputs.contains(&name) {
+                inputs.push(name);
+            }
+        }
+
+        let mut outputs: Vec<&str> = Vec::new();
+        for rule in &self.rules {
+            if !outputs.contains(&rule.consequence.name.as_str()) {
+                outputs.push(&rule.consequence.name);
+            }
+        }
+
+        let mut dot = String::from("digraph rules {\n    rankdir=LR;\n");
+
+        for (i, name) in inputs.iter().enumerate() {
+            let _ = writeln!(dot, "    input_{} [label={}, shape=ellipse];", i, dot_quote(name));
+        }
+
+        for (i, rule) in self.rules.iter().enumerate() {
+            let label = rule.name.clone().unwrap_or_else(|| format!("rule {}", i));
+            let _ = writeln!(dot, "    rule_{} [label={}, shape=box];", i, dot_quote(&label));
+        }
+
+        for (i, name) in outputs.iter().enumerate() {
+            let _ = writeln!(dot, "    output_{} [label={}, shape=doublecircle];", i, dot_quote(name));
+        }
+
+        for (i, rule) in self.rules.iter().enumerate() {
+            for name in rule_inputs.get(i).copied().unwrap_or(&[]) {
+                if let Some(input) = inputs.iter().position(|input| input == name) {
+                    let _ = writeln!(dot, "    input_{} -> rule_{};", input, i);
+                }
+            }
+
+            if let Some(output) = outputs.iter().position(|name| *name == rule.consequence.name) {
+                let _ = writeln!(dot, "    rule_{} -> output_{};", i, output);
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub fn rule_conflict_score(&self, domain: &Domain) -> Vec<Vec<f64>> {
+        let strengths: Vec<Vec<f64>> = self
+            .rules
+            .iter()
+            .map(|rule| {
+                domain
+                    .points()
+                    .map(|x| self.validate_input(x).map_or(0.0, |x| rule.firing_strength(x)))
+                    .collect()
+            })
+            .collect();
+
+        strengths
+            .iter()
+            .enumerate()
+            .map(|(i, a)| {
+                strengths
+                    .iter()
+                    .enumerate()
+                    .map(|(j, b)| {
+                        if i == j {
+                            return 1.0;
+                        }
+
+                        let (intersection, union) = a.iter().zip(b).fold((0.0, 0.0), |(i, u), (x, y)| {
+                            (i + f64::min(*x, *y), u + f64::max(*x, *y))
+                        });
+
+                        if union == 0.0 {
+                            0.0
+                        } else {
+                            intersection / union
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn most_applicable_rule(&self, input: f64) -> Option<(&FuzzyRule, f64)> {
+        self.top_rules(input, 1).into_iter().next()
+    }
+
+    pub fn top_rules(&self, input: f64, k: usize) -> Vec<(&FuzzyRule, f64)> {
+        let input = match self.validate_input(input) {
+            Ok(input) => input,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut fired: Vec<(&FuzzyRule, f64)> = self
+            .rules
+            .iter()
+            .filter_map(|rule| rule.evaluate(input).map(|(_, weight)| (rule, weight)))
+            .collect();
+
+        fired.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+        fired.truncate(k);
+        fired
+    }
+
+    pub fn rules_for_consequence<'a>(&'a self, consequence: &str) -> Vec<&'a FuzzyRule> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.consequence.name == consequence)
+            .collect()
+    }
+
+    pub fn consequence_counts(&self) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+
+        for rule in &self.rules {
+            *counts.entry(rule.consequence.name.clone()).or_insert(0) += 1;
+        }
+
+        counts
+    }
+
+    pub fn rules_grouped_by_consequence(&self) -> HashMap<String, Vec<&FuzzyRule>> {
+        let mut groups: HashMap<String, Vec<&FuzzyRule>> = HashMap::new();
+
+        for rule in &self.rules {
+            groups.entry(rule.consequence.name.clone()).or_default().push(rule);
+        }
+
+        groups
+    }
+
+    pub fn to_lookup_table(&self, input_range: &Domain) -> LookupTable {
+        LookupTable {
+            domain: *input_range,
+            outputs: input_range.points().map(|x| self.infer_numeric(x)).collect(),
+        }
+    }
+
+    pub fn rule_interaction_matrix(&self, input: f64) -> Vec<Vec<f64>> {
+        let weights: Vec<f64> = match self.validate_input(input) {
+            Ok(input) => self
+                .rules
+                .iter()
+                .map(|rule| rule.evaluate(input).map_or(0.0, |(_, weight)| weight))
+                .collect(),
+            Err(_) => vec![0.0; self.rules.len()],
+        };
+
+        weights
+            .iter()
+            .map(|a| weights.iter().map(|b| a * b).collect())
+            .collect()
+    }
+
+    fn check_rule_base(&self) -> Result<(), FuzzyError> {
+        if !cfg!(debug_assertions) || self.rules.is_empty() {
+            return Ok(());
+        }
+
+        self.validate()
+            .map_err(|errors| errors.into_iter().next().unwrap_or(FuzzyError::NoRules))
+    }
+
+    fn validate_input(&self, input: f64) -> Result<f64, FuzzyError> {
+        let (min, max) = match self.input_range {
+            Some(range) => range,
+            None => return Ok(input),
+        };
+
+        if input >= min && input <= max {
+            return Ok(input);
+        }
+
+        match self.validation_mode {
+            InputValidationMode::Clamp => Ok(input.clamp(min, max)),
+            InputValidationMode::Reject => Err(FuzzyError::InputOutOfRange { input, min, max }),
+            InputValidationMode::Warn => {
+                if let Some(callback) = &self.warning_callback {
+                    callback(input);
+                }
+                Ok(input)
+            }
+        }
+    }
+
+    fn output_gradients(&self, input: f64) -> (f64, Vec<f64>) {
+        let output = self.infer_numeric(input);
+        let mut gradients = vec![0.0; self.rules.len()];
+
+        let input = match self.check_rule_base().and_then(|_| self.validate_input(input)) {
+            Ok(input) => input,
+            Err(_) => return (output, gradients),
+        };
+
+        let (indices, results): (Vec<usize>, Vec<(FuzzySet, f64)>) = self
+            .rules
+            .iter()
+            .enumerate()
+            .filter_map(|(i, rule)| rule.evaluate(input).map(|result| (i, result)))
+            .unzip();
+        let score = match self.weighted_score(&results) {
+            Some(score) => score,
+            None => return (output, gradients),
+        };
+
+        let range = self.selected_range(&results);
+        let total_weight: f64 = results[range.clone()].iter().map(|(_, weight)| weight).sum();
+        let h = 1e-6;
+        let slope = (self.transform_output(score + h) - self.transform_output(score - h)) / (2.0 * h);
+
+        for k in range {
+            let rule = &self.rules[indices[k]];
+            let activation = rule.firing_strength(input) * rule.confidence;
+            let priority = self.priority_mapping(&results[k].0.name);
+            gradients[indices[k]] = slope * activation * (priority - score) / total_weight;
+        }
+
+        (output, gradients)
+    }
+
+    fn evaluate_rules(&self, input: f64) -> Vec<(FuzzySet, f64)> {
+        self.rules
+            .iter()
+            .filter_map(|rule| rule.evaluate(input))
+            .collect()
+    }
+
+    fn select_results<'a>(&self, results: &'a [(FuzzySet, f64)]) -> &'a [(FuzzySet, f64)] {
+        &results[self.selected_range(results)]
+    }
+
+    fn selected_range(&self, results: &[(FuzzySet, f64)]) -> std::ops::Range<usize> {
+        let fired = results.iter().position(|(_, weight)| *weight > 0.0);
+
+        let index = match self.inference_mode {
+            InferenceMode::WeightedAggregate => return 0..results.len(),
+            InferenceMode::FirstMatch => fired,
+            InferenceMode::LastMatch => results.iter().rposition(|(_, weight)| *weight > 0.0),
+            InferenceMode::BestMatch => fired.map(|first| {
+                (first..results.len()).fold(first, |best, i| {
+                    if results[i].1 > results[best].1 {
+                        i
+                    } else {
+                        best
+                    }
+                })
+            }),
+        };
+
+        match index {
+            Some(i) => i..i + 1,
+            None => 0..0,
+        }
+    }
+
+    fn weighted_score(&self, results: &[(FuzzySet, f64)]) -> Option<f64> {
+        let mut total_weight = 0.0;
+        let mut weighted_sum = 0.0;
+
+        for (result, weight) in self.select_results(results) {
+            weighted_sum += self.priority_mapping(&result.name) * weight;
+            total_weight += weight;
+        }
+
+        if total_weight > 0.0 {
+            Some(weighted_sum / total_weight)
+        } else {
+            None
+        }
+    }
+
+    fn aggregate_results(&self, results: &[(FuzzySet, f64)]) -> String {
+        match self.weighted_score(results) {
+            Some(score) => self.reverse_priority_mapping(self.transform_output(score)),
+            None => self.default_output.clone(),
+        }
+    }
+
+    fn transform_output(&self, score: f64) -> f64 {
+        match &self.output_transformer {
+            Some(transformer) => transformer(score),
+            None => score,
+        }
+    }
+
+    fn priority_mapping(&self, priority: &str) -> f64 {
+        match priority {
+            "Urgent" => 3.0,
+            "High Priority" => 2.0,
+            "Medium Priority" => 1.0,
+            _ => 0.0,
+        }
+    }
+
+    fn reverse_priority_mapping(&self, score: f64) -> String {
+        match score.partial_cmp(&2.5) {
+            Some(Ordering::Greater) | Some(Ordering::Equal) => "Urgent".to_string(),
+            _ => match score.partial_cmp(&1.5) {
+                Some(Ordering::Greater) | Some(Ordering::Equal) => "High Priority".to_string(),
+                _ => match score.partial_cmp(&0.5) {
+                    Some(Ordering::Greater) | Some(Ordering::Equal) => "Medium Priority".to_string(),
+                    _ => "Low Priority".to_string(),
+                },
+            },
+        }
+    }
+}
+
+impl Extend<FuzzyRule> for InferenceEngine {
+    fn extend<I: IntoIterator<Item = FuzzyRule>>(&mut self, rules: I) {
+        self.rules.extend(rules);
+    }
+}
+
+pub struct InferenceEngineChain {
+    stages: Vec<InferenceEngine>,
+    output_to_input_map: Vec<fn(String) -> f64>,
+}
+
+impl InferenceEngineChain {
+    pub fn new(first_stage: InferenceEngine) -> Self {
+        InferenceEngineChain {
+            stages: vec![first_stage],
+            output_to_input_map: Vec::new(),
+        }
+    }
+
+    pub fn then(mut self, output_to_input: fn(String) -> f64, stage: InferenceEngine) -> Self {
+        self.output_to_input_map.push(output_to_input);
+        self.stages.push(stage);
+        self
+    }
+
+    pub fn stage_count(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn chain_infer(&self, initial_input: f64) -> Vec<String> {
+        self.explain_chain(initial_input)
+            .into_iter()
+            .map(|trace| trace.output)
+            .collect()
+    }
+
+    pub fn explain_chain(&self, initial_input: f64) -> Vec<InferenceTrace> {
+        let mut traces: Vec<InferenceTrace> = Vec::with_capacity(self.stages.len());
+        let mut input = initial_input;
+
+        for (i, stage) in self.stages.iter().enumerate() {
+            if i > 0 {
+                let previous = traces[i - 1].output.clone();
+                input = (self.output_to_input_map[i - 1])(previous);
+            }
+
+            traces.push(stage.trace(input));
+        }
+
+        traces
+    }
+}
+
+pub struct FuzzyPIDController {
+    engine: InferenceEngine,
+    kp: f64,
+    ki: f64,
+    kd: f64,
+    integral: f64,
+    previous_error: Option<f64>,
+}
+
+impl FuzzyPIDController {
+    pub fn new(engine: InferenceEngine, kp: f64, ki: f64, kd: f64) -> Self {
+        FuzzyPIDController {
+            engine,
+            kp,
+            ki,
+            kd,
+            integral: 0.0,
+            previous_error: None,
+        }
+    }
+
+    pub fn engine(&self) -> &InferenceEngine {
+        &self.engine
+    }
+
+    pub fn integral(&self) -> f64 {
+        self.integral
+    }
+
+    pub fn reset(&mut self) {
+        self.integral = 0.0;
+        self.previous_error = None;
+    }
+
+    pub fn compute_output(&mut self, error: f64, dt: f64) -> f64 {
+        self.integral += error * dt;
+
+        let derivative = match self.previous_error {
+            Some(previous) if dt > 0.0 => (error - previous) / dt,
+            _ => 0.0,
+        };
+        self.previous_error = Some(error);
+
+        let signal = self.kp * error + self.ki * self.integral + self.kd * derivative;
+        self.engine.infer_numeric(signal)
+    }
+}
+
+pub struct LookupTable {
+    domain: Domain,
+    outputs: Vec<f64>,
+}
+
+impl LookupTable {
+    pub fn domain(&self) -> &Domain {
+        &self.domain
+    }
+
+    pub fn outputs(&self) -> &[f64] {
+        &self.outputs
+    }
+
+    pub fn query(&self, input: f64) -> f64 {
+        let last = match self.outputs.len() {
+            0 => return 0.0,
+            len => len - 1,
+        };
+
+        let position = ((input - self.domain.min()) / self.domain.step()).clamp(0.0, last as f64);
+        let index = usize::min(position.floor() as usize, last);
+
+        if index == last {
+            return self.outputs[last];
+        }
+
+        let fraction = position - index as f64;
+        self.outputs[index] + (self.outputs[index + 1] - self.outputs[index]) * fraction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_domain() -> Domain {
+        Domain::new(0.0, 1.0, 0.01).unwrap()
+    }
+
+    fn crisp_band(name: &str, lower: f64, upper: f64) -> FuzzySet {
+        FuzzySet::new(
+            name,
+            Arc::new(move |x| if (lower..=upper).contains(&x) { 1.0 } else { 0.0 }),
+        )
+    }
+
+    fn priority_engine() -> InferenceEngine {
+        InferenceEngine::new(vec![
+            FuzzyRule::new(
+                Box::new(|x| x > 0.7),
+                FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3),
+                1.0,
+            )
+            .with_name("very high"),
+            FuzzyRule::new(
+                Box::new(|x| x > 0.4),
+                FuzzySet::triangular("High Priority", 0.4, 0.7, 1.0),
+                0.5,
+            )
+            .with_name("high"),
+            FuzzyRule::new(
+                Box::new(|x| x <= 0.4),
+                FuzzySet::triangular("Medium Priority", 0.1, 0.4, 0.7),
+                1.0,
+            )
+            .with_name("low"),
+        ])
+    }
+
+    #[test]
+    fn triangular_support_and_core_stay_within_parameters() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+
+        let support = set.support(&domain);
+        assert!(!support.is_empty());
+        assert!(support.iter().all(|&x| x > 0.2 && x < 0.8));
+        assert!(set.has_nonempty_support(&domain));
+
+        let core = set.core(&domain);
+        assert!(core.iter().any(|&x| (x - 0.5).abs() < 1e-9));
+        assert!(core.iter().all(|&x| (x - 0.5).abs() < 1e-9));
+    }
+
+    #[test]
+    fn empty_set_has_no_support() {
+        let set = FuzzySet::new("empty", Arc::new(|_| 0.0));
+
+        assert!(set.support(&unit_domain()).is_empty());
+        assert!(!set.has_nonempty_support(&unit_domain()));
+        assert!(set.core(&unit_domain()).is_empty());
+    }
+
+    #[test]
+    fn overlap_of_identical_sets_is_one() {
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+
+        assert!((set.overlap_measure(&set, &unit_domain()) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn overlap_with_complement_of_crisp_set_is_zero() {
+        let set = crisp_band("band", 0.3, 0.7);
+
+        assert_eq!(set.overlap_measure(&set.complement(), &unit_domain()), 0.0);
+    }
+
+    #[test]
+    fn overlap_with_empty_set_is_zero() {
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let empty = FuzzySet::new("empty", Arc::new(|_| 0.0));
+
+        assert_eq!(set.overlap_measure(&empty, &unit_domain()), 0.0);
+    }
+
+    #[test]
+    fn dice_similarity_of_set_with_itself_is_one() {
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+
+        assert!((set.dice_similarity(&set, &unit_domain()) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn dice_similarity_with_complement_of_crisp_set_is_zero() {
+        let set = crisp_band("band", 0.3, 0.7);
+
+        assert_eq!(set.dice_similarity(&set.complement(), &unit_domain()), 0.0);
+    }
+
+    #[test]
+    fn dice_similarity_is_at_least_jaccard_similarity() {
+        let domain = unit_domain();
+        let sets = [
+            FuzzySet::triangular("left", 0.0, 0.25, 0.5),
+            FuzzySet::triangular("mid", 0.2, 0.5, 0.8),
+            FuzzySet::gaussian("wide", 0.6, 0.3),
+            crisp_band("band", 0.3, 0.7),
+        ];
+
+        for a in &sets {
+            for b in &sets {
+                let dice = a.dice_similarity(b, &domain);
+                let jaccard = a.jaccard_similarity(b, &domain);
+                assert!((0.0..=1.0).contains(&dice));
+                assert!(dice >= jaccard - 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn bounding_box_matches_support_of_triangle() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let support = set.support(&domain);
+
+        let (left, right) = set.bounding_box(&domain).unwrap();
+        assert_eq!(left, support[0]);
+        assert_eq!(right, support[support.len() - 1]);
+        assert!((set.diameter(&domain) - (right - left)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn bounding_box_of_empty_set_is_none() {
+        let set = FuzzySet::new("empty", Arc::new(|_| 0.0));
+
+        assert_eq!(set.bounding_box(&unit_domain()), None);
+        assert_eq!(set.diameter(&unit_domain()), 0.0);
+    }
+
+    #[test]
+    fn explain_lists_each_fired_rule_and_matches_infer() {
+        let engine = priority_engine();
+        let explanation = engine.explain(0.8);
+
+        assert_eq!(explanation.lines().filter(|line| line.contains(": FIRED with weight")).count(), 2);
+        assert!(explanation.contains("Rule very high: FIRED with weight 1.00, consequence Urgent"));
+        assert!(explanation.contains(&format!("Output: {}", engine.infer(0.8))));
+    }
+
+    #[test]
+    fn explain_reports_when_no_rule_fires() {
+        let engine = InferenceEngine::new(Vec::new());
+
+        assert!(engine.explain(0.5).contains("No rules fired"));
+    }
+
+    #[test]
+    fn domain_rejects_empty_or_degenerate_ranges() {
+        assert_eq!(
+            Domain::new(0.0, 0.0, 0.1).unwrap_err(),
+            FuzzyError::InvalidDomain { min: 0.0, max: 0.0, step: 0.1 }
+        );
+        assert!(Domain::new(1.0, 0.0, 0.1).is_err());
+        assert!(Domain::new(0.0, 1.0, 0.0).is_err());
+        assert!(Domain::new(0.0, 1.0, 1.0).is_err());
+        assert!(Domain::new(0.0, f64::NAN, 0.1).is_err());
+    }
+
+    #[test]
+    fn domain_samples_both_endpoints() {
+        let domain = Domain::new(0.0, 1.0, 0.25).unwrap();
+
+        assert_eq!(domain.sample_count(), 5);
+        assert_eq!(domain.points().collect::<Vec<_>>(), vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn defuzzify_centroid_accepts_tuples_and_domains() {
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let from_domain = set.defuzzify_centroid(unit_domain()).unwrap();
+        let from_tuple = set.defuzzify_centroid((0.0, 1.0, 0.01)).unwrap();
+
+        assert_eq!(from_domain, from_tuple);
+        assert!((from_tuple - 0.5).abs() < 1e-9);
+        assert!(set.defuzzify_centroid((0.0, 0.0, 0.1)).is_err());
+        assert!(Domain::try_from((0.0, 1.0, 0.01)).is_ok());
+    }
+
+    #[test]
+    fn area_between_set_and_itself_is_zero() {
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+
+        assert_eq!(set.area_between(&set, &unit_domain()), 0.0);
+        assert_eq!(set.absolute_area_between(&set, &unit_domain()), 0.0);
+    }
+
+    #[test]
+    fn area_between_point_symmetric_set_and_complement_cancels() {
+        let domain = unit_domain();
+        let set = FuzzySet::sigmoidal("rising", 10.0, 0.5);
+        let complement = set.complement();
+
+        assert!(set.area_between(&complement, &domain).abs() < 1e-9);
+        assert!(set.absolute_area_between(&complement, &domain) > 0.1);
+    }
+
+    #[test]
+    fn area_between_is_antisymmetric() {
+        let domain = unit_domain();
+        let a = FuzzySet::triangular("left", 0.0, 0.25, 0.5);
+        let b = FuzzySet::gaussian("wide", 0.6, 0.3);
+
+        assert!((a.area_between(&b, &domain) + b.area_between(&a, &domain)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn product_with_itself_equals_concentrate() {
+        let set = FuzzySet::gaussian("wide", 0.6, 0.3);
+        let product = set.product(&set);
+        let concentrated = set.concentrate();
+
+        for x in unit_domain().points() {
+            assert!((product.membership_degree(x) - concentrated.membership_degree(x)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn product_of_complementary_sets_is_not_zero_everywhere() {
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let product = set.product(&set.complement());
+
+        assert!(unit_domain().points().any(|x| product.membership_degree(x) > 0.0));
+    }
+
+    #[test]
+    fn product_clamps_but_unclamped_product_does_not() {
+        let double = FuzzySet::new("double", Arc::new(|_| 2.0));
+
+        assert_eq!(double.product(&double).membership_degree(0.5), 1.0);
+        assert_eq!(double.product_unclamped(&double).membership_degree(0.5), 4.0);
+    }
+
+    #[test]
+    fn intersection_product_is_product() {
+        let a = FuzzySet::triangular("left", 0.0, 0.25, 0.5);
+        let b = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+
+        for x in unit_domain().points() {
+            assert_eq!(
+                a.intersection_product(&b).membership_degree(x),
+                a.product(&b).membership_degree(x)
+            );
+        }
+    }
+
+    #[test]
+    fn bounded_sum_is_dual_of_lukasiewicz_intersection() {
+        let a = FuzzySet::triangular("left", 0.0, 0.25, 0.5);
+        let b = FuzzySet::gaussian("wide", 0.4, 0.2);
+        let sum = a.sum_bounded(&b);
+        let dual = a.complement().intersection_lukasiewicz(&b.complement()).complement();
+
+        for x in unit_domain().points() {
+            assert!((sum.membership_degree(x) - dual.membership_degree(x)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn algebraic_sum_is_dual_of_product() {
+        let a = FuzzySet::triangular("left", 0.0, 0.25, 0.5);
+        let b = FuzzySet::gaussian("wide", 0.4, 0.2);
+        let sum = a.sum_algebraic(&b);
+        let dual = a.complement().product(&b.complement()).complement();
+
+        for x in unit_domain().points() {
+            let mu = sum.membership_degree(x);
+            assert!((0.0..=1.0).contains(&mu));
+            assert!((mu - dual.membership_degree(x)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn engine_without_matching_rules_returns_configured_defaults() {
+        let engine = InferenceEngine::new(vec![FuzzyRule::new(
+            Box::new(|x| x > 2.0),
+            FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3),
+            1.0,
+        )])
+        .with_default_output("Idle".to_string())
+        .with_default_numeric(-1.0);
+
+        assert_eq!(engine.infer(0.5), "Idle");
+        assert_eq!(engine.infer_numeric(0.5), -1.0);
+        assert_eq!(engine.infer(3.0), "Urgent");
+    }
+
+    #[test]
+    fn engine_defaults_to_low_priority() {
+        let engine = InferenceEngine::new(Vec::new());
+
+        assert_eq!(engine.infer(0.5), "Low Priority");
+        assert_eq!(engine.infer_numeric(0.5), 0.0);
+    }
+
+    #[test]
+    fn weighted_centroid_with_universal_weight_is_centroid() {
+        let domain = unit_domain();
+        let set = FuzzySet::gaussian("wide", 0.6, 0.2);
+        let universal = FuzzySet::new("universal", Arc::new(|_| 1.0));
+
+        assert!((set.weighted_centroid(&universal, &domain) - set.centroid(&domain)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn weighted_centroid_moves_toward_weighted_region() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let right = FuzzySet::sigmoidal("right", 10.0, 0.5);
+
+        assert!(set.weighted_centroid(&right, &domain) > set.centroid(&domain));
+    }
+
+    #[test]
+    fn normalize_to_centroid_moves_centroid_to_target() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.4, 0.6);
+        let moved = set.normalize_to_centroid(0.6, &domain);
+
+        assert!((moved.centroid(&domain) - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn normalize_to_centroid_only_shifts_the_shape() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.4, 0.6);
+        let offset = 0.6 - set.centroid(&domain);
+        let moved = set.normalize_to_centroid(0.6, &domain);
+
+        for x in domain.points() {
+            assert!((moved.membership_degree(x + offset) - set.membership_degree(x)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn shouldered_triangles_form_ruspini_partition() {
+        let domain = unit_domain();
+        let low = FuzzySet::triangular("low", -0.5, 0.0, 0.5);
+        let mid = FuzzySet::triangular("mid", 0.0, 0.5, 1.0);
+        let high = FuzzySet::triangular("high", 0.5, 1.0, 1.5);
+
+        assert!(FuzzySet::is_ruspini_partition(&[&low, &mid, &high], &domain, 1e-9));
+
+        let extra = FuzzySet::triangular("extra", 0.2, 0.4, 0.6);
+        assert!(!FuzzySet::is_ruspini_partition(&[&low, &mid, &high, &extra], &domain, 1e-9));
+    }
+
+    #[test]
+    fn crisp_set_is_at_zero_distance_from_crisp() {
+        let set = crisp_band("band", 0.3, 0.7);
+
+        assert_eq!(set.distance_to_nearest_crisp(&unit_domain()), 0.0);
+    }
+
+    #[test]
+    fn fuzzy_set_is_at_positive_distance_from_crisp() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.0, 0.5, 1.0);
+        let expected: f64 = domain
+            .points()
+            .map(|x| {
+                let mu = set.membership_degree(x);
+                mu.min(1.0 - mu) * domain.step()
+            })
+            .sum();
+
+        assert!((set.distance_to_nearest_crisp(&domain) - expected).abs() < 1e-12);
+        assert!(expected > 0.0);
+    }
+
+    #[test]
+    fn threshold_to_crisp_is_alpha_cut() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.0, 0.5, 1.0);
+        let crisp = set.threshold_to_crisp(0.5, &domain);
+
+        assert_eq!(crisp, set.alpha_cut(0.5, &domain));
+        assert!(crisp.iter().all(|&x| (0.25 - 1e-9..=0.75 + 1e-9).contains(&x)));
+    }
+
+    #[test]
+    fn soft_threshold_at_zero_matches_infer() {
+        let engine = priority_engine();
+
+        for input in unit_domain().points() {
+            assert_eq!(engine.soft_threshold_infer(input, 0.0), engine.infer(input));
+        }
+    }
+
+    #[test]
+    fn soft_threshold_ignores_rules_below_threshold() {
+        let hot = FuzzySet::triangular("hot", 0.5, 1.0, 1.5);
+        let engine = InferenceEngine::new(vec![FuzzyRule::new_graded(
+            Box::new(move |x| hot.membership_degree(x)),
+            FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3),
+            1.0,
+        )]);
+
+        assert_eq!(engine.soft_threshold_infer(0.8, 0.7), "Low Priority");
+        assert_eq!(engine.soft_threshold_infer(0.95, 0.7), "Urgent");
+    }
+
+    #[test]
+    fn sample_visits_every_domain_point() {
+        let domain = Domain::new(0.0, 1.0, 0.25).unwrap();
+        let set = FuzzySet::triangular("mid", 0.0, 0.5, 1.0);
+
+        assert_eq!(
+            set.sample(&domain),
+            vec![(0.0, 0.0), (0.25, 0.5), (0.5, 1.0), (0.75, 0.5), (1.0, 0.0)]
+        );
+        assert_eq!(set.sample_membership_only(&domain), vec![0.0, 0.5, 1.0, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn centroid_mom_and_bisector_agree_with_samples() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("skewed", 0.1, 0.3, 0.9);
+        let samples = set.sample(&domain);
+
+        let mass: f64 = samples.iter().map(|(_, mu)| mu).sum();
+        let moment: f64 = samples.iter().map(|(x, mu)| x * mu).sum();
+        assert!((set.centroid(&domain) - moment / mass).abs() < 1e-12);
+
+        let peak = samples.iter().find(|(_, mu)| *mu == 1.0).unwrap().0;
+        assert_eq!(set.defuzzify_mom(&domain), Some(peak));
+
+        let bisector = set.defuzzify_bisector(&domain).unwrap();
+        let left: f64 = samples.iter().filter(|(x, _)| *x <= bisector).map(|(_, mu)| mu).sum();
+        assert!((left / mass - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn triangle_reaches_full_and_zero_membership() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.0, 0.5, 1.0);
+
+        assert_eq!(set.max_membership(&domain), 1.0);
+        assert_eq!(set.min_membership(&domain), 0.0);
+        assert_eq!(set.height(&domain), 1.0);
+        assert!(set.is_normal(&domain));
+        assert!(!set.is_subnormal(&domain));
+    }
+
+    #[test]
+    fn scaled_set_is_subnormal() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.0, 0.5, 1.0).scale(0.6);
+
+        assert!((set.max_membership(&domain) - 0.6).abs() < 1e-12);
+        assert!(set.is_subnormal(&domain));
+    }
+
+    fn priority_registry() -> ConditionRegistry {
+        let mut registry = ConditionRegistry::new();
+        registry.register("very high", Arc::new(|x| x > 0.8));
+        registry.register("high", Arc::new(|x| x > 0.6));
+        registry.register("medium", Arc::new(|x| x > 0.4));
+        registry.register_graded("low", Arc::new(|x| (1.0 - x).clamp(0.0, 1.0)));
+        registry.register("any", Arc::new(|_| true));
+        registry.register_consequence(FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3));
+        registry.register_consequence(FuzzySet::triangular("High Priority", 0.4, 0.7, 1.0));
+        registry.register_consequence(FuzzySet::triangular("Medium Priority", 0.1, 0.4, 0.7));
+        registry
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialized_rules_round_trip_through_registry() {
+        let registry = priority_registry();
+        let rules = [
+            ("very high", "Urgent", 1.0, 1.0),
+            ("high", "High Priority", 0.8, 0.9),
+            ("medium", "Medium Priority", 0.6, 1.0),
+            ("low", "Medium Priority", 0.4, 0.5),
+            ("any", "Medium Priority", 0.1, 1.0),
+        ];
+        let rules = rules
+            .iter()
+            .map(|&(name, consequence, weight, confidence)| {
+                registry
+                    .build_rule(name, consequence, weight)
+                    .unwrap()
+                    .with_confidence(confidence)
+                    .unwrap()
+            })
+            .collect();
+        let engine = InferenceEngine::new(rules);
+
+        let json = engine.serialize_rules_json().unwrap();
+        let restored = InferenceEngine::deserialize_rules_json(&json, &registry).unwrap();
+
+        assert_eq!(restored.rule_count(), 5);
+        assert_eq!(restored.serialize_rules_json().unwrap(), json);
+        for input in unit_domain().points() {
+            assert_eq!(restored.infer(input), engine.infer(input));
+            assert_eq!(restored.infer_numeric(input), engine.infer_numeric(input));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializing_unnamed_rule_fails() {
+        let engine = InferenceEngine::new(vec![FuzzyRule::new(
+            Box::new(|_| true),
+            FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3),
+            1.0,
+        )]);
+
+        assert_eq!(
+            engine.serialize_rules_json().unwrap_err().to_string(),
+            FuzzyError::UnnamedRule(0).to_string()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserializing_rejects_unknown_names_bad_weights_and_deep_nesting() {
+        let registry = priority_registry();
+
+        assert_eq!(
+            InferenceEngine::deserialize_rules_json(
+                r#"{"rules":[{"name":"missing","weight":1,"consequence":"Urgent"}]}"#,
+                &registry
+            )
+            .err(),
+            Some(FuzzyError::UnknownCondition("missing".to_string()))
+        );
+        assert_eq!(
+            InferenceEngine::deserialize_rules_json(
+                r#"{"rules":[{"name":"high","weight":-1,"consequence":"Urgent"}]}"#,
+                &registry
+            )
+            .err(),
+            Some(FuzzyError::InvalidWeight(-1.0))
+        );
+
+        let nested = format!("{}{}", "[".repeat(10_000), "]".repeat(10_000));
+        assert!(matches!(
+            InferenceEngine::deserialize_rules_json(&nested, &registry),
+            Err(FuzzyError::Deserialization(_))
+        ));
+    }
+
+    #[test]
+    fn max_aggregation_matches_union_all() {
+        let a = FuzzySet::triangular("left", 0.0, 0.25, 0.5);
+        let b = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let c = FuzzySet::gaussian("wide", 0.7, 0.1);
+        let aggregated = FuzzySet::aggregate("all", &[&a, &b, &c], ConormStrategy::Max);
+        let union = FuzzySet::union_all("all", &[&a, &b, &c]);
+
+        for x in unit_domain().points() {
+            assert_eq!(aggregated.membership_degree(x), union.membership_degree(x));
+        }
+    }
+
+    #[test]
+    fn conorm_aggregations_stay_in_unit_interval_and_dominate_max() {
+        let a = FuzzySet::triangular("left", 0.0, 0.25, 0.5);
+        let b = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let max = FuzzySet::aggregate("max", &[&a, &b], ConormStrategy::Max);
+
+        for strategy in [ConormStrategy::AlgebraicSum, ConormStrategy::BoundedSum, ConormStrategy::EinsteinSum] {
+            let aggregated = FuzzySet::aggregate("sum", &[&a, &b], strategy);
+
+            for x in unit_domain().points() {
+                let mu = aggregated.membership_degree(x);
+                assert!((0.0..=1.0 + 1e-12).contains(&mu));
+                assert!(mu >= max.membership_degree(x) - 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn weighted_aggregation_scales_each_set() {
+        let a = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let aggregated = FuzzySet::aggregate_with_weights("half", &[(&a, 0.5)], ConormStrategy::Max);
+
+        assert_eq!(aggregated.membership_degree(0.5), 0.5);
+    }
+
+    #[test]
+    fn first_mean_and_last_of_maximum_are_ordered() {
+        let domain = unit_domain();
+        let sets = [
+            FuzzySet::trapezoidal("plateau", 0.1, 0.3, 0.6, 0.9),
+            FuzzySet::triangular("skewed", 0.1, 0.3, 0.9),
+            crisp_band("band", 0.3, 0.7),
+        ];
+
+        for set in &sets {
+            let fom = set.defuzzify_fom(&domain).unwrap();
+            let mom = set.defuzzify_mom(&domain).unwrap();
+            let lom = set.defuzzify_lom(&domain).unwrap();
+            assert!(fom <= mom && mom <= lom);
+            assert!((mom - (fom + lom) / 2.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn strictly_unimodal_set_has_single_maximum() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+
+        assert_eq!(set.defuzzify_fom(&domain), set.defuzzify_lom(&domain));
+        assert_eq!(set.defuzzify_mom(&domain), set.defuzzify_fom(&domain));
+    }
+
+    #[test]
+    fn maximum_methods_need_nonempty_support() {
+        let set = FuzzySet::new("empty", Arc::new(|_| 0.0));
+
+        assert_eq!(set.defuzzify_fom(&unit_domain()), None);
+        assert_eq!(set.defuzzify_mom(&unit_domain()), None);
+        assert_eq!(set.defuzzify_lom(&unit_domain()), None);
+    }
+
+    #[test]
+    fn concentrate_then_dilate_is_close_to_identity() {
+        let set = FuzzySet::gaussian("wide", 0.5, 0.2);
+        let composed = set.compose_with(&[MembershipOp::Concentrate, MembershipOp::Dilate]);
+
+        for x in unit_domain().points() {
+            assert!((composed.membership_degree(x) - set.membership_degree(x)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn double_complement_is_identity() {
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let composed = set.compose_with(&[MembershipOp::Complement, MembershipOp::Complement]);
+
+        for x in unit_domain().points() {
+            assert!((composed.membership_degree(x) - set.membership_degree(x)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn composed_ops_apply_left_to_right() {
+        let set = FuzzySet::triangular("mid", 0.0, 0.2, 0.4);
+        let composed = set.compose_with(&[MembershipOp::Shift(0.3), MembershipOp::Clip(0.8)]);
+
+        assert!((composed.membership_degree(0.5) - 0.8).abs() < 1e-12);
+        assert_eq!(composed.membership_degree(0.2), 0.0);
+    }
+
+    #[test]
+    fn clamp_mode_clamps_out_of_range_inputs() {
+        let engine = priority_engine().with_input_validation((0.0, 0.5), InputValidationMode::Clamp);
+
+        assert_eq!(engine.infer_numeric(0.9), priority_engine().infer_numeric(0.5));
+        assert_eq!(engine.infer(0.9), priority_engine().infer(0.5));
+    }
+
+    #[test]
+    fn reject_mode_reports_out_of_range_inputs() {
+        let engine = priority_engine()
+            .with_input_validation((0.0, 1.0), InputValidationMode::Reject)
+            .with_default_output("Rejected".to_string());
+
+        assert_eq!(
+            engine.try_infer(1.5),
+            Err(FuzzyError::InputOutOfRange { input: 1.5, min: 0.0, max: 1.0 })
+        );
+        assert_eq!(engine.infer(1.5), "Rejected");
+        assert_eq!(engine.soft_threshold_infer(1.5, 0.0), "Rejected");
+        assert_eq!(engine.try_infer(0.8), Ok("Urgent".to_string()));
+    }
+
+    #[test]
+    fn warn_mode_reports_and_keeps_out_of_range_inputs() {
+        let warnings = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&warnings);
+        let engine = priority_engine()
+            .with_input_validation((0.0, 1.0), InputValidationMode::Warn)
+            .with_warning_callback(Arc::new(move |input| sink.lock().unwrap().push(input)));
+
+        assert_eq!(engine.infer(1.5), priority_engine().infer(1.5));
+        assert_eq!(engine.infer(0.5), priority_engine().infer(0.5));
+        assert_eq!(*warnings.lock().unwrap(), vec![1.5]);
+    }
+
+    #[test]
+    fn convolving_with_singleton_at_zero_preserves_set() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let singleton = FuzzySet::new("zero", Arc::new(|y: f64| if y.abs() < 1e-9 { 1.0 } else { 0.0 }));
+        let convolved = set.convolve(&singleton, &domain, &domain);
+
+        for x in domain.points() {
+            assert!((convolved.membership_degree(x) - set.membership_degree(x)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn convolving_triangles_adds_their_parameters() {
+        let domain = unit_domain();
+        let a = FuzzySet::triangular("a", 0.0, 0.2, 0.4);
+        let b = FuzzySet::triangular("b", 0.1, 0.3, 0.5);
+        let expected = FuzzySet::triangular("sum", 0.1, 0.5, 0.9);
+        let convolved = a.convolve(&b, &domain, &domain);
+
+        for z in domain.points() {
+            assert!((convolved.membership_degree(z) - expected.membership_degree(z)).abs() < 0.03);
+        }
+    }
+
+    fn jagged_set() -> FuzzySet {
+        FuzzySet::new(
+            "jagged",
+            Arc::new(|x: f64| 0.5 + 0.3 * ((x * 100.0).round() as i64 % 2) as f64),
+        )
+    }
+
+    fn difference_variance(values: &[f64]) -> f64 {
+        let diffs: Vec<f64> = values.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        let mean = diffs.iter().sum::<f64>() / diffs.len() as f64;
+        diffs.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / diffs.len() as f64
+    }
+
+    #[test]
+    fn moving_average_reduces_jaggedness() {
+        let domain = unit_domain();
+        let set = jagged_set();
+        let smoothed = set.moving_average_smooth(5, &domain).unwrap();
+
+        assert!(
+            difference_variance(&smoothed.sample_membership_only(&domain))
+                < difference_variance(&set.sample_membership_only(&domain))
+        );
+    }
+
+    #[test]
+    fn moving_average_window_must_fit_the_domain() {
+        let domain = Domain::new(0.0, 1.0, 0.25).unwrap();
+        let set = jagged_set();
+
+        assert_eq!(
+            set.moving_average_smooth(0, &domain).err(),
+            Some(FuzzyError::InvalidWindow { window: 0, sample_count: 5 })
+        );
+        assert!(set.moving_average_smooth(6, &domain).is_err());
+        assert!(set.moving_average_smooth(5, &domain).is_ok());
+    }
+
+    #[test]
+    fn moving_average_window_is_centered() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let smoothed = set.moving_average_smooth(5, &domain).unwrap();
+
+        assert!((smoothed.centroid(&domain) - set.centroid(&domain)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn gaussian_smoothing_turns_a_step_into_an_s_curve() {
+        let domain = unit_domain();
+        let step = FuzzySet::new("step", Arc::new(|x| if x >= 0.5 { 1.0 } else { 0.0 }));
+        let smoothed = step.gaussian_smooth(0.05, &domain);
+
+        assert!(smoothed.membership_degree(0.3) < 0.05);
+        assert!((smoothed.membership_degree(0.5) - 0.5).abs() < 0.1);
+        assert!(smoothed.membership_degree(0.7) > 0.95);
+
+        let rising: Vec<f64> = domain
+            .points()
+            .filter(|x| (0.2..=0.7).contains(x))
+            .map(|x| smoothed.membership_degree(x))
+            .collect();
+        assert!(rising.windows(2).all(|pair| pair[1] >= pair[0] - 1e-12));
+    }
+
+    #[test]
+    fn gaussian_smoothing_roughly_preserves_area() {
+        let domain = Domain::new(-1.0, 2.0, 0.01).unwrap();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let smoothed = set.gaussian_smooth(0.05, &domain);
+
+        assert!((smoothed.integrate(&domain) - set.integrate(&domain)).abs() < 0.05);
+        assert!((smoothed.max_membership(&domain) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn zero_confidence_rule_has_no_effect() {
+        let base = priority_engine();
+        let mut engine = priority_engine();
+        engine.add_rule(
+            FuzzyRule::new(Box::new(|_| true), FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3), 5.0)
+                .with_confidence(0.0)
+                .unwrap(),
+        );
+
+        for input in unit_domain().points() {
+            assert_eq!(engine.infer_numeric(input), base.infer_numeric(input));
+            assert_eq!(engine.infer(input), base.infer(input));
+        }
+    }
+
+    #[test]
+    fn confidence_outside_unit_interval_is_rejected() {
+        let rule = || FuzzyRule::new(Box::new(|_| true), FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3), 1.0);
+
+        assert!(matches!(rule().with_confidence(1.5), Err(FuzzyError::InvalidConfidence(c)) if c == 1.5));
+        assert!(rule().with_confidence(-0.1).is_err());
+        assert_eq!(rule().with_confidence(0.25).unwrap().confidence(), 0.25);
+    }
+
+    #[test]
+    fn possibility_peaks_at_modal_value() {
+        let domain = unit_domain();
+        let distribution =
+            PossibilityDistribution::new(FuzzySet::triangular("mid", 0.2, 0.5, 0.8), &domain).unwrap();
+
+        assert_eq!(distribution.possibility(0.5), 1.0);
+        assert_eq!(distribution.possibility(0.9), 0.0);
+        assert!((distribution.consistency_with(&distribution, &domain) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn necessity_grows_with_the_event() {
+        let domain = unit_domain();
+        let distribution =
+            PossibilityDistribution::new(FuzzySet::triangular("mid", 0.2, 0.5, 0.8), &domain).unwrap();
+
+        let necessities: Vec<f64> = [0.05, 0.15, 0.25, 0.35]
+            .iter()
+            .map(|&radius| distribution.necessity(&CrispSet::interval(0.5 - radius, 0.5 + radius), &domain))
+            .collect();
+
+        assert!(necessities.windows(2).all(|pair| pair[1] >= pair[0]));
+        assert!(necessities[0] < necessities[3]);
+        assert!((necessities[3] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn possibility_distribution_must_be_normal() {
+        let domain = unit_domain();
+        let subnormal = FuzzySet::triangular("mid", 0.2, 0.5, 0.8).scale(0.5);
+
+        assert!(matches!(
+            PossibilityDistribution::new(subnormal, &domain),
+            Err(FuzzyError::NotNormal { .. })
+        ));
+    }
+
+    #[test]
+    fn interaction_matrix_is_symmetric_with_squared_diagonal() {
+        let engine = priority_engine();
+        let matrix = engine.rule_interaction_matrix(0.8);
+        let weights = [1.0, 0.5, 0.0];
+
+        assert_eq!(matrix.len(), 3);
+        for (i, row) in matrix.iter().enumerate() {
+            assert_eq!(row[i], weights[i] * weights[i]);
+            for (j, &value) in row.iter().enumerate() {
+                assert_eq!(value, matrix[j][i]);
+            }
+        }
+        assert_eq!(matrix[0][1], 0.5);
+    }
+
+    #[test]
+    fn idle_rules_have_zero_rows_and_columns() {
+        let matrix = priority_engine().rule_interaction_matrix(0.8);
+
+        assert!(matrix[2].iter().all(|&value| value == 0.0));
+        assert!(matrix.iter().all(|row| row[2] == 0.0));
+    }
+
+    #[test]
+    fn rejected_input_gives_zero_interaction_matrix() {
+        let engine = priority_engine().with_input_validation((0.0, 1.0), InputValidationMode::Reject);
+
+        assert_eq!(engine.rule_interaction_matrix(2.0), vec![vec![0.0; 3]; 3]);
+    }
+
+    #[test]
+    fn restrict_is_membership_degree() {
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+
+        for x in unit_domain().points() {
+            assert_eq!(set.restrict(x), set.membership_degree(x));
+        }
+    }
+
+    #[test]
+    fn conditional_possibility_is_at_most_unconditional_possibility() {
+        let domain = unit_domain();
+        let distribution = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let events = [
+            FuzzySet::triangular("left", 0.0, 0.2, 0.4),
+            FuzzySet::triangular("right", 0.5, 0.8, 1.0).scale(0.7),
+            crisp_band("band", 0.45, 0.55),
+        ];
+
+        for event in &events {
+            let conditional = distribution.conditional_possibility(event, &domain);
+            assert!(conditional <= event.height(&domain) + 1e-12);
+        }
+        assert!((distribution.conditional_possibility(&events[2], &domain) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn conditional_possibility_of_empty_distribution_is_zero() {
+        let empty = FuzzySet::new("empty", Arc::new(|_| 0.0));
+
+        assert_eq!(empty.conditional_possibility(&crisp_band("band", 0.0, 1.0), &unit_domain()), 0.0);
+    }
+
+    #[test]
+    fn gauss_legendre_centroid_reaches_high_accuracy_with_twenty_points() {
+        let set = FuzzySet::gaussian("wide", 4.0, 2.0);
+        let exact = 4.101_565_979_349_758;
+        let quadrature = set.defuzzify_centroid_gauss_legendre(0.0, 10.0, 20).unwrap();
+        let uniform = set.centroid(&Domain::new(0.0, 10.0, 0.001).unwrap());
+
+        assert!((quadrature - exact).abs() < 1e-10);
+        assert!((quadrature - exact).abs() < (uniform - exact).abs());
+    }
+
+    #[test]
+    fn gauss_legendre_centroid_of_symmetric_set_is_its_center() {
+        let set = FuzzySet::gaussian("centered", 5.0, 1.0);
+
+        for n in [5, 10, 20, 50] {
+            let centroid = set.defuzzify_centroid_gauss_legendre(0.0, 10.0, n).unwrap();
+            assert!((centroid - 5.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn gauss_legendre_centroid_rejects_bad_arguments() {
+        let set = FuzzySet::gaussian("wide", 4.0, 2.0);
+
+        assert_eq!(
+            set.defuzzify_centroid_gauss_legendre(1.0, 1.0, 20),
+            Err(FuzzyError::InvalidInterval { min: 1.0, max: 1.0 })
+        );
+        assert!(matches!(
+            set.defuzzify_centroid_gauss_legendre(0.0, 10.0, 7),
+            Err(FuzzyError::InvalidParameters(_))
+        ));
+    }
+
+    fn counted(set: FuzzySet) -> (FuzzySet, Arc<std::sync::atomic::AtomicUsize>) {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let counted = FuzzySet::new(
+            "counted",
+            Arc::new(move |x| {
+                counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                set.membership_degree(x)
+            }),
+        );
+
+        (counted, calls)
+    }
+
+    #[test]
+    fn adaptive_centroid_converges_on_narrow_gaussian() {
+        let (set, calls) = counted(FuzzySet::gaussian("narrow", 0.43, 0.01));
+        let centroid = set.defuzzify_centroid_adaptive(0.0, 1.0, 1e-8, 50).unwrap();
+        let adaptive_calls = calls.swap(0, std::sync::atomic::Ordering::Relaxed);
+
+        set.centroid(&Domain::new(0.0, 1.0, 0.001).unwrap());
+        let fixed_calls = calls.load(std::sync::atomic::Ordering::Relaxed);
+
+        assert!((centroid - 0.43).abs() < 1e-8);
+        assert!(adaptive_calls < fixed_calls);
+    }
+
+    #[test]
+    fn adaptive_centroid_reports_failures() {
+        let set = FuzzySet::gaussian("narrow", 0.43, 0.01);
+        let empty = FuzzySet::new("empty", Arc::new(|_| 0.0));
+
+        assert_eq!(
+            set.defuzzify_centroid_adaptive(1.0, 0.0, 1e-8, 50),
+            Err(FuzzyError::InvalidInterval { min: 1.0, max: 0.0 })
+        );
+        assert_eq!(
+            set.defuzzify_centroid_adaptive(0.0, 1.0, 1e-14, 2),
+            Err(FuzzyError::NotConverged { max_depth: 2 })
+        );
+        assert_eq!(empty.defuzzify_centroid_adaptive(0.0, 1.0, 1e-8, 50), Err(FuzzyError::EmptySupport));
+    }
+
+    fn graded_engine(low_weight: f64, high_weight: f64) -> InferenceEngine {
+        InferenceEngine::new(vec![
+            FuzzyRule::new_graded(
+                Box::new(|x| (1.0 - x).clamp(0.0, 1.0)),
+                FuzzySet::triangular("Medium Priority", 0.1, 0.4, 0.7),
+                low_weight,
+            )
+            .with_name("low"),
+            FuzzyRule::new_graded(
+                Box::new(|x| x.clamp(0.0, 1.0)),
+                FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3),
+                high_weight,
+            )
+            .with_name("high"),
+        ])
+    }
+
+    fn graded_samples(low_weight: f64, high_weight: f64) -> Vec<(f64, f64)> {
+        let target = graded_engine(low_weight, high_weight);
+
+        (1..10)
+            .map(|i| i as f64 / 10.0)
+            .map(|x| (x, target.infer_numeric(x)))
+            .collect()
+    }
+
+    #[test]
+    fn tune_weights_reduces_training_loss() {
+        let data = graded_samples(1.0, 0.3);
+        let mut engine = graded_engine(1.0, 1.0);
+        let losses = engine.tune_weights(&data, 0.5, 50);
+
+        assert_eq!(losses.len(), 50);
+        assert!(losses.windows(2).all(|pair| pair[1] <= pair[0] + 1e-12));
+        assert!(losses[49] < losses[0] / 10.0);
+        assert!(engine.get_rule_weights()[1] < 1.0);
+    }
+
+    #[test]
+    fn tune_weights_keeps_weights_non_negative() {
+        let data = graded_samples(1.0, 0.01);
+        let mut engine = graded_engine(1.0, 1.0);
+        engine.tune_weights(&data, 50.0, 20);
+
+        assert!(engine.get_rule_weights().iter().all(|&weight| weight >= 0.0));
+    }
+
+    #[test]
+    fn constructors_report_their_kind() {
+        let piecewise = FuzzySet::from_points("points", &[(0.0, 0.0), (0.5, 1.0), (1.0, 0.0)]).unwrap();
+
+        assert_eq!(FuzzySet::triangular("t", 0.0, 0.5, 1.0).kind(), MembershipFnKind::Triangular);
+        assert_eq!(FuzzySet::trapezoidal("z", 0.0, 0.2, 0.8, 1.0).kind(), MembershipFnKind::Trapezoidal);
+        assert_eq!(FuzzySet::gaussian("g", 0.5, 0.1).kind(), MembershipFnKind::Gaussian);
+        assert_eq!(FuzzySet::sigmoidal("s", 10.0, 0.5).kind(), MembershipFnKind::Sigmoidal);
+        assert_eq!(FuzzySet::bell("b", 0.2, 2.0, 0.5).kind(), MembershipFnKind::Bell);
+        assert_eq!(piecewise.kind(), MembershipFnKind::PiecewiseLinear);
+        assert_eq!(FuzzySet::new("c", Arc::new(|_| 1.0)).kind(), MembershipFnKind::Custom);
+    }
+
+    #[test]
+    fn derived_sets_are_custom() {
+        let set = FuzzySet::triangular("t", 0.0, 0.5, 1.0);
+
+        assert_eq!(set.complement().kind(), MembershipFnKind::Custom);
+        assert_eq!(set.union(&set).kind(), MembershipFnKind::Custom);
+    }
+
+    #[test]
+    fn parameter_vector_round_trips_through_from_parameters() {
+        let sets = [
+            FuzzySet::triangular("t", 0.0, 0.5, 1.0),
+            FuzzySet::trapezoidal("z", 0.0, 0.2, 0.8, 1.0),
+            FuzzySet::gaussian("g", 0.5, 0.1),
+            FuzzySet::sigmoidal("s", 10.0, 0.5),
+            FuzzySet::bell("b", 0.2, 2.0, 0.5),
+            FuzzySet::from_points("p", &[(0.0, 0.0), (0.5, 1.0), (1.0, 0.0)]).unwrap(),
+        ];
+
+        for set in &sets {
+            let params = set.parameter_vector().unwrap();
+            let rebuilt = FuzzySet::from_parameters(set.kind(), set.name(), &params).unwrap();
+            assert_eq!(rebuilt.parameter_vector(), Some(params));
+            for x in unit_domain().points() {
+                assert_eq!(rebuilt.membership_degree(x), set.membership_degree(x));
+            }
+        }
+        assert_eq!(FuzzySet::triangular("t", 0.0, 0.5, 1.0).parameter_vector(), Some(vec![0.0, 0.5, 1.0]));
+        assert_eq!(FuzzySet::gaussian("g", 0.5, 0.1).parameter_vector(), Some(vec![0.5, 0.1]));
+    }
+
+    #[test]
+    fn custom_sets_have_no_parameters() {
+        let set = FuzzySet::new("c", Arc::new(|_| 1.0));
+
+        assert_eq!(set.parameter_vector(), None);
+        assert!(FuzzySet::from_parameters(MembershipFnKind::Custom, "c", &[]).is_err());
+    }
+
+    #[test]
+    fn from_parameters_checks_parameter_count() {
+        assert!(matches!(
+            FuzzySet::from_parameters(MembershipFnKind::Triangular, "t", &[0.0, 1.0]),
+            Err(FuzzyError::InvalidParameters(_))
+        ));
+        assert!(FuzzySet::from_parameters(MembershipFnKind::PiecewiseLinear, "p", &[0.0, 0.0, 1.0]).is_err());
+    }
+
+    #[test]
+    fn optimize_parameters_recovers_gaussian_shape() {
+        let target = FuzzySet::gaussian("target", 0.5, 0.1);
+        let data: Vec<(f64, f64)> = Domain::new(0.0, 1.0, 0.05)
+            .unwrap()
+            .points()
+            .map(|x| (x, target.membership_degree(x)))
+            .collect();
+        let mut set = FuzzySet::gaussian("guess", 0.42, 0.14);
+        let losses = set.optimize_parameters(&data, 0.01, 200).unwrap();
+        let params = set.parameter_vector().unwrap();
+
+        assert!(losses[199] < losses[0] * 1e-6);
+        assert!((params[0] - 0.5).abs() < 1e-3);
+        assert!((params[1] - 0.1).abs() < 1e-3);
+        assert_eq!(set.name(), "guess");
+    }
+
+    #[test]
+    fn optimize_parameters_keeps_piecewise_memberships_in_range() {
+        let data = [(0.0, 1.0), (0.5, 1.0), (1.0, 1.0)];
+        let mut set = FuzzySet::from_points("p", &[(0.0, 0.9), (0.5, 0.95), (1.0, 0.9)]).unwrap();
+        set.optimize_parameters(&data, 5.0, 20).unwrap();
+
+        let params = set.parameter_vector().unwrap();
+        assert!(params.iter().skip(1).step_by(2).all(|mu| (0.0..=1.0).contains(mu)));
+    }
+
+    #[test]
+    fn custom_sets_cannot_be_optimized() {
+        let mut set = FuzzySet::new("c", Arc::new(|_| 1.0));
+
+        assert!(matches!(
+            set.optimize_parameters(&[(0.0, 1.0)], 0.1, 1),
+            Err(FuzzyError::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn cross_validation_on_noise_free_data_has_tiny_error() {
+        let data = graded_samples(1.0, 0.3);
+        let errors = graded_engine(1.0, 1.0).cross_validate(&data, 3, 0.5, 300);
+
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().all(|&error| error < 1e-4));
+    }
+
+    #[test]
+    fn cross_validation_skips_empty_folds_and_leaves_engine_untouched() {
+        let data = graded_samples(1.0, 0.3);
+        let engine = graded_engine(1.0, 1.0);
+        let errors = engine.cross_validate(&data[..2], 4, 0.5, 10);
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(engine.get_rule_weights(), vec![1.0, 1.0]);
+    }
+
+    #[test]
+    fn partial_integrals_are_additive() {
+        let set = FuzzySet::gaussian("wide", 0.4, 0.2);
+        let left = set.partial_integral(0.0, 0.3, 0.001);
+        let right = set.partial_integral(0.3, 1.0, 0.001);
+
+        assert!((left + right - set.partial_integral(0.0, 1.0, 0.001)).abs() < 1e-9);
+        assert!((set.partial_integral(1.0, 0.0, 0.001) + set.partial_integral(0.0, 1.0, 0.001)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn triangle_integral_is_half_base_times_height() {
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+
+        assert!((set.partial_integral(0.0, 1.0, 0.01) - 0.3).abs() < 1e-9);
+        assert_eq!(set.partial_integral(0.5, 0.5, 0.01), 0.0);
+    }
+
+    #[test]
+    fn cumulative_distribution_rises_from_zero_to_one() {
+        let cumulative = FuzzySet::triangular("mid", 0.2, 0.5, 0.8).cumulative_distribution(&unit_domain());
+
+        assert_eq!(cumulative[0].1, 0.0);
+        assert!((cumulative[cumulative.len() - 1].1 - 1.0).abs() < 1e-12);
+        assert!(cumulative.windows(2).all(|pair| pair[1].1 >= pair[0].1));
+        let middle = cumulative.iter().find(|(x, _)| (x - 0.5).abs() < 1e-9).unwrap();
+        assert!((middle.1 - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn percentile_spans_the_domain_and_matches_bisector() {
+        let domain = unit_domain();
+        let set = FuzzySet::gaussian("wide", 0.4, 0.5);
+
+        assert!((set.percentile(0.0, &domain).unwrap() - domain.min()).abs() < 1e-9);
+        assert!((set.percentile(1.0, &domain).unwrap() - domain.max()).abs() < 1e-9);
+        assert_eq!(set.percentile(0.5, &domain).ok(), set.defuzzify_bisector(&domain));
+    }
+
+    #[test]
+    fn percentiles_are_ordered() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("skewed", 0.1, 0.3, 0.9);
+        let values: Vec<f64> = [0.1, 0.25, 0.5, 0.75, 0.9]
+            .iter()
+            .map(|&p| set.percentile(p, &domain).unwrap())
+            .collect();
+
+        assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn percentile_rejects_bad_fractions_and_empty_sets() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let empty = FuzzySet::new("empty", Arc::new(|_| 0.0));
+
+        assert_eq!(set.percentile(1.5, &domain), Err(FuzzyError::InvalidPercentile(1.5)));
+        assert_eq!(set.percentile(-0.1, &domain), Err(FuzzyError::InvalidPercentile(-0.1)));
+        assert_eq!(empty.percentile(0.5, &domain), Err(FuzzyError::EmptySupport));
+        assert_eq!(empty.defuzzify_bisector(&domain), None);
+    }
+
+    #[test]
+    fn triangle_iqr_follows_from_its_area_quartiles() {
+        let domain = Domain::new(0.0, 1.0, 0.001).unwrap();
+        let set = FuzzySet::triangular("mid", 0.0, 0.5, 1.0);
+        let expected = 1.0 - std::f64::consts::FRAC_1_SQRT_2;
+
+        assert!((set.iqr(&domain).unwrap() - expected).abs() < 1e-3);
+        assert!((set.bandwidth(&domain) - 0.5).abs() < 1e-3);
+        assert!((set.spread_ratio(&domain) - expected / 0.5).abs() < 1e-2);
+    }
+
+    #[test]
+    fn flatter_trapezoid_has_wider_iqr() {
+        let domain = unit_domain();
+        let triangle = FuzzySet::triangular("peak", 0.1, 0.5, 0.9);
+        let trapezoid = FuzzySet::trapezoidal("flat", 0.1, 0.3, 0.7, 0.9);
+
+        assert!(trapezoid.iqr(&domain).unwrap() > triangle.iqr(&domain).unwrap());
+    }
+
+    #[test]
+    fn spread_ratio_of_empty_set_is_zero() {
+        let empty = FuzzySet::new("empty", Arc::new(|_| 0.0));
+
+        assert!(empty.iqr(&unit_domain()).is_err());
+        assert_eq!(empty.spread_ratio(&unit_domain()), 0.0);
+    }
+
+    #[test]
+    fn output_distribution_sums_to_one_and_favours_inferred_label() {
+        let engine = priority_engine();
+
+        for input in unit_domain().points() {
+            let distribution = engine.output_distribution(input);
+            let total: f64 = distribution.values().sum();
+            let (top, _) = distribution
+                .iter()
+                .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+                .unwrap();
+
+            assert!((total - 1.0).abs() < 1e-12);
+            assert_eq!(top, &engine.infer(input));
+        }
+    }
+
+    #[test]
+    fn output_distribution_shares_follow_rule_weights() {
+        let distribution = priority_engine().output_distribution(0.8);
+
+        assert_eq!(distribution.len(), 2);
+        assert!((distribution["Urgent"] - 2.0 / 3.0).abs() < 1e-12);
+        assert!((distribution["High Priority"] - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(priority_engine().infer_with_confidence(0.8).0, "Urgent");
+    }
+
+    #[test]
+    fn output_distribution_is_empty_when_nothing_fires() {
+        assert!(InferenceEngine::new(Vec::new()).output_distribution(0.5).is_empty());
+    }
+
+    #[test]
+    fn transform_codomain_with_one_minus_is_complement() {
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let transformed = set.transform_codomain(|v| 1.0 - v);
+        let complement = set.complement();
+
+        for x in unit_domain().points() {
+            assert_eq!(transformed.membership_degree(x), complement.membership_degree(x));
+        }
+    }
+
+    #[test]
+    fn checked_codomain_transform_rejects_out_of_range_memberships() {
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+
+        assert!(set.transform_codomain_checked(|v| v * v, &unit_domain()).is_ok());
+        assert!(matches!(
+            set.transform_codomain_checked(|v| 2.0 * v, &unit_domain()),
+            Err(FuzzyError::MembershipOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn crisp_threshold_has_only_zero_and_one() {
+        let domain = unit_domain();
+        let thresholded = FuzzySet::gaussian("wide", 0.5, 0.2).apply_threshold(0.6);
+        let values = thresholded.sample_membership_only(&domain);
+
+        assert!(values.iter().all(|&mu| mu == 0.0 || mu == 1.0));
+        assert!(values.contains(&0.0) && values.contains(&1.0));
+    }
+
+    #[test]
+    fn sigmoid_threshold_is_half_at_alpha() {
+        let set = FuzzySet::triangular("mid", 0.0, 0.5, 1.0);
+        let soft = set.apply_sigmoid_threshold(50.0, 0.5);
+
+        assert!((soft.membership_degree(0.25) - 0.5).abs() < 1e-12);
+        assert!(soft.membership_degree(0.5) > 0.99);
+        assert!(soft.membership_degree(0.05) < 0.01);
+    }
+
+    #[test]
+    fn output_fuzzy_set_height_is_largest_fired_weight() {
+        let domain = Domain::new(0.0, 1.5, 0.01).unwrap();
+        let engine = InferenceEngine::new(vec![
+            FuzzyRule::new(Box::new(|x| x > 0.7), FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3), 0.6),
+            FuzzyRule::new(Box::new(|x| x > 0.4), FuzzySet::triangular("High Priority", 0.4, 0.7, 1.0), 0.3),
+            FuzzyRule::new(Box::new(|x| x <= 0.4), FuzzySet::triangular("Medium Priority", 0.1, 0.4, 0.7), 0.9),
+        ]);
+
+        assert!((engine.output_fuzzy_set(0.8).height(&domain) - 0.6).abs() < 1e-9);
+        assert!((engine.output_fuzzy_set(0.5).height(&domain) - 0.3).abs() < 1e-9);
+        assert!((engine.output_fuzzy_set(0.2).height(&domain) - 0.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn output_fuzzy_set_is_empty_when_nothing_fires() {
+        let output = InferenceEngine::new(Vec::new()).output_fuzzy_set(0.5);
+
+        assert!(!output.has_nonempty_support(&unit_domain()));
+    }
+
+    #[test]
+    fn reducing_a_single_set_returns_it() {
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let tnorm = FuzzySet::tnorm_reduce("t", std::iter::once(&set), TNorm::Product).unwrap();
+        let conorm = FuzzySet::conorm_reduce("s", std::iter::once(&set), ConormStrategy::AlgebraicSum).unwrap();
+
+        for x in unit_domain().points() {
+            assert_eq!(tnorm.membership_degree(x), set.membership_degree(x));
+            assert_eq!(conorm.membership_degree(x), set.membership_degree(x));
+        }
+    }
+
+    #[test]
+    fn reducing_three_sets_matches_pairwise_reduction() {
+        let a = FuzzySet::triangular("a", 0.0, 0.4, 0.8);
+        let b = FuzzySet::triangular("b", 0.1, 0.5, 0.9);
+        let c = FuzzySet::triangular("c", 0.2, 0.6, 1.0);
+        let sets = [&a, &b, &c];
+
+        for norm in [TNorm::Min, TNorm::Product, TNorm::Lukasiewicz, TNorm::EinsteinProduct] {
+            let reduced = FuzzySet::tnorm_reduce("t", sets.iter().copied(), norm).unwrap();
+            for x in unit_domain().points() {
+                let pairwise = norm.apply(norm.apply(a.membership_degree(x), b.membership_degree(x)), c.membership_degree(x));
+                assert!((reduced.membership_degree(x) - pairwise).abs() < 1e-12);
+            }
+        }
+
+        let union = FuzzySet::conorm_reduce("s", sets.iter().copied(), ConormStrategy::Max).unwrap();
+        let pairwise = a.union(&b).union(&c);
+        for x in unit_domain().points() {
+            assert_eq!(union.membership_degree(x), pairwise.membership_degree(x));
+        }
+    }
+
+    #[test]
+    fn reducing_no_sets_gives_none() {
+        assert!(FuzzySet::tnorm_reduce("t", std::iter::empty(), TNorm::Min).is_none());
+        assert!(FuzzySet::conorm_reduce("s", std::iter::empty(), ConormStrategy::Max).is_none());
+    }
+
+    #[test]
+    fn centroid_variance_shrinks_with_finer_steps() {
+        let set = FuzzySet::triangular("skewed", 0.1234, 0.3711, 0.9087);
+        let variances: Vec<f64> = [0.1, 0.03, 0.01, 0.003]
+            .iter()
+            .map(|&step| set.centroid_variance(&Domain::new(0.0, 1.0, step).unwrap(), 3))
+            .collect();
+
+        assert!(variances.windows(2).all(|pair| pair[1] < pair[0]));
+        assert_eq!(set.centroid_variance(&unit_domain(), 0), 0.0);
+    }
+
+    #[test]
+    fn centroid_with_error_meets_tolerance() {
+        let set = FuzzySet::triangular("skewed", 0.13, 0.37, 0.91);
+        let exact = (0.13 + 0.37 + 0.91) / 3.0;
+        let (centroid, error) = set.centroid_with_error(&Domain::new(0.0, 1.0, 0.1).unwrap(), 1e-6);
+
+        assert!(error <= 1e-6);
+        assert!((centroid - exact).abs() < 1e-4);
+    }
+
+    #[test]
+    fn alpha_cut_intervals_shrink_as_alpha_grows() {
+        let domain = unit_domain();
+        let set = FuzzySet::gaussian("wide", 0.5, 0.15);
+        let intervals: Vec<(f64, f64)> = [0.1, 0.3, 0.5, 0.7, 0.9]
+            .iter()
+            .map(|&alpha| set.alpha_cut_interval(alpha, &domain).unwrap())
+            .collect();
+
+        assert!(intervals
+            .windows(2)
+            .all(|pair| pair[1].0 >= pair[0].0 && pair[1].1 <= pair[0].1 && pair[1].1 - pair[1].0 < pair[0].1 - pair[0].0));
+        assert_eq!(set.alpha_cut_interval(1.5, &domain), None);
+    }
+
+    #[test]
+    fn for_alpha_cuts_visits_nonempty_cuts_in_order() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let mut visited = Vec::new();
+        set.for_alpha_cuts(&[0.25, 0.75, 1.5], &domain, |alpha, interval| visited.push((alpha, interval)));
+
+        assert_eq!(visited.len(), 2);
+        assert_eq!(visited[0], (0.25, set.alpha_cut_interval(0.25, &domain).unwrap()));
+        assert_eq!(visited[1].0, 0.75);
+    }
+
+    #[test]
+    fn interval_representation_is_nested() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("skewed", 0.1, 0.3, 0.9);
+        let alphas = [0.0, 0.2, 0.4, 0.6, 0.8, 1.0];
+        let representation = set.interval_representation(&alphas, &domain);
+        let intervals: Vec<(f64, f64)> = representation.iter().map(|(_, interval)| interval.unwrap()).collect();
+
+        assert_eq!(representation.iter().map(|(alpha, _)| *alpha).collect::<Vec<_>>(), alphas);
+        assert!(intervals.windows(2).all(|pair| pair[0].0 <= pair[1].0 && pair[1].1 <= pair[0].1));
+    }
+
+    #[test]
+    fn zero_alpha_interval_is_support() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let support = set.support(&domain);
+
+        assert_eq!(
+            set.interval_representation(&[0.0], &domain)[0].1,
+            Some((support[0], support[support.len() - 1]))
+        );
+    }
+
+    #[test]
+    fn extension_principle_adds_triangular_numbers() {
+        let domain = unit_domain();
+        let a = FuzzySet::triangular("a", 0.0, 0.2, 0.4);
+        let b = FuzzySet::triangular("b", 0.1, 0.3, 0.5);
+        let expected = FuzzySet::triangular("sum", 0.1, 0.5, 0.9);
+        let sum = FuzzySet::extension_principle_2d(&a, &b, |x, y| x + y, &domain, &domain, &domain);
+
+        for z in domain.points() {
+            assert!((sum.membership_degree(z) - expected.membership_degree(z)).abs() < 0.03);
+        }
+        assert!((sum.membership_degree(0.5) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn extension_principle_truncates_to_output_domain() {
+        let domain = unit_domain();
+        let a = FuzzySet::triangular("a", 0.4, 0.6, 0.8);
+        let sum = FuzzySet::extension_principle_2d(&a, &a, |x, y| x + y, &domain, &domain, &domain);
+
+        assert_eq!(sum.membership_degree(0.7), 0.0);
+        assert!((sum.membership_degree(1.0) - 0.5).abs() < 0.03);
+        assert_eq!(sum.membership_degree(1.2), 0.0);
+    }
+
+    #[test]
+    fn input_sensitivity_is_zero_when_conditions_ignore_the_input() {
+        let domain = Domain::new(0.0, 1.5, 0.01).unwrap();
+        let engine = InferenceEngine::new(vec![
+            FuzzyRule::new(Box::new(|_| true), FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3), 0.6),
+            FuzzyRule::new_graded(
+                Box::new(|_| 0.4),
+                FuzzySet::triangular("High Priority", 0.4, 0.7, 1.0),
+                1.0,
+            ),
+        ]);
+
+        assert_eq!(engine.input_sensitivity_fuzzy(0.2, 0.5, &domain), 0.0);
+    }
+
+    #[test]
+    fn input_sensitivity_detects_changes_in_fired_rules() {
+        let domain = Domain::new(0.0, 1.5, 0.01).unwrap();
+        let engine = priority_engine();
+
+        assert_eq!(engine.input_sensitivity_fuzzy(0.5, 0.1, &domain), 0.0);
+        assert!(engine.input_sensitivity_fuzzy(0.65, 0.1, &domain) > 0.0);
+    }
+
+    #[test]
+    fn membership_inverses_find_crossover_points() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+
+        assert!((set.membership_inverse_rising(0.5, &domain).unwrap() - 0.35).abs() < 1e-9);
+        assert!((set.membership_inverse_falling(0.5, &domain).unwrap() - 0.65).abs() < 1e-9);
+
+        let gaussian = FuzzySet::gaussian("g", 0.5, 0.1);
+        let crossover = gaussian.membership_inverse_rising(0.5, &domain).unwrap();
+        assert!((crossover - (0.5 - 0.1 * (2.0 * std::f64::consts::LN_2).sqrt())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn membership_inverse_rejects_unreachable_degrees() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8).scale(0.5);
+
+        assert_eq!(set.membership_inverse_rising(0.8, &domain), None);
+        assert_eq!(set.membership_inverse_falling(-0.1, &domain), None);
+    }
+
+    #[test]
+    fn conditioning_on_universal_set_returns_self() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let universal = FuzzySet::new("universal", Arc::new(|_| 1.0));
+        let conditional = set.conditional_set(&universal, &domain);
+
+        for x in domain.points() {
+            assert_eq!(conditional.membership_degree(x), set.membership_degree(x));
+            assert_eq!(set.conditional_membership(&universal, x, &domain), set.membership_degree(x));
+        }
+    }
+
+    #[test]
+    fn conditioning_on_singleton_returns_singleton() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("wide", 0.0, 0.5, 1.0);
+        let point = FuzzySet::new("point", Arc::new(|x: f64| if (x - 0.3).abs() < 1e-9 { 1.0 } else { 0.0 }));
+        let conditional = set.conditional_set(&point, &domain);
+        let support = conditional.support(&domain);
+
+        assert_eq!(support.len(), 1);
+        assert!((support[0] - 0.3).abs() < 1e-9);
+        assert!((conditional.membership_degree(support[0]) - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn conditioning_on_empty_set_is_zero() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let empty = FuzzySet::new("empty", Arc::new(|_| 0.0));
+
+        assert_eq!(set.conditional_membership(&empty, 0.5, &domain), 0.0);
+        assert!(!set.conditional_set(&empty, &domain).has_nonempty_support(&domain));
+    }
+
+    #[test]
+    fn single_element_t_norm_chain_is_the_set() {
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let chain = FuzzySet::t_norm_chain("chain", &[(&set, TNorm::Product)]);
+
+        for x in unit_domain().points() {
+            assert_eq!(chain.membership_degree(x), set.membership_degree(x));
+        }
+    }
+
+    #[test]
+    fn all_min_t_norm_chain_is_intersection() {
+        let a = FuzzySet::triangular("a", 0.0, 0.4, 0.8);
+        let b = FuzzySet::triangular("b", 0.1, 0.5, 0.9);
+        let c = FuzzySet::triangular("c", 0.2, 0.6, 1.0);
+        let chain = FuzzySet::t_norm_chain("chain", &[(&a, TNorm::Min), (&b, TNorm::Min), (&c, TNorm::Min)]);
+        let intersection = a.intersection(&b).intersection(&c);
+
+        for x in unit_domain().points() {
+            assert_eq!(chain.membership_degree(x), intersection.membership_degree(x));
+        }
+    }
+
+    #[test]
+    fn t_norm_chain_applies_each_norm_to_the_running_result() {
+        let a = FuzzySet::triangular("a", 0.0, 0.4, 0.8);
+        let b = FuzzySet::triangular("b", 0.1, 0.5, 0.9);
+        let c = FuzzySet::triangular("c", 0.2, 0.6, 1.0);
+        let chain = FuzzySet::t_norm_chain("chain", &[(&a, TNorm::Min), (&b, TNorm::Product), (&c, TNorm::Lukasiewicz)]);
+
+        for x in unit_domain().points() {
+            let expected = TNorm::Lukasiewicz.apply(a.membership_degree(x) * b.membership_degree(x), c.membership_degree(x));
+            assert!((chain.membership_degree(x) - expected).abs() < 1e-12);
+        }
+        assert_eq!(FuzzySet::t_norm_chain("empty", &[]).membership_degree(0.5), 1.0);
+    }
+
+    #[test]
+    fn single_fired_rule_is_most_applicable() {
+        let engine = priority_engine();
+        let (rule, weight) = engine.most_applicable_rule(0.2).unwrap();
+
+        assert_eq!(rule.name(), Some("low"));
+        assert_eq!(weight, 1.0);
+    }
+
+    #[test]
+    fn top_rules_are_ordered_by_effective_weight() {
+        let engine = priority_engine();
+        let top: Vec<(Option<&str>, f64)> = engine
+            .top_rules(0.8, 5)
+            .into_iter()
+            .map(|(rule, weight)| (rule.name(), weight))
+            .collect();
+
+        assert_eq!(top, vec![(Some("very high"), 1.0), (Some("high"), 0.5)]);
+        assert_eq!(engine.top_rules(0.8, 1).len(), 1);
+        assert_eq!(engine.most_applicable_rule(0.8).unwrap().0.name(), Some("very high"));
+    }
+
+    #[test]
+    fn no_applicable_rule_when_nothing_fires() {
+        assert!(InferenceEngine::new(Vec::new()).most_applicable_rule(0.5).is_none());
+    }
+
+    fn max_second_difference(set: &FuzzySet, domain: &Domain) -> f64 {
+        set.sample_membership_only(domain)
+            .windows(3)
+            .map(|w| (w[2] - 2.0 * w[1] + w[0]).abs())
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn cubic_spline_passes_through_control_points() {
+        let points = [(0.0, 0.0), (0.25, 0.6), (0.5, 1.0), (0.75, 0.6), (1.0, 0.0)];
+        let spline = FuzzySet::cubic_spline_interpolation("spline", &points).unwrap();
+
+        for &(x, mu) in &points {
+            assert!((spline.membership_degree(x) - mu).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn cubic_spline_is_smoother_than_piecewise_linear() {
+        let domain = unit_domain();
+        let points = [(0.0, 0.0), (0.25, 0.6), (0.5, 1.0), (0.75, 0.6), (1.0, 0.0)];
+        let spline = FuzzySet::cubic_spline_interpolation("spline", &points).unwrap();
+        let linear = FuzzySet::from_points("linear", &points).unwrap();
+
+        assert!(max_second_difference(&spline, &domain) < max_second_difference(&linear, &domain));
+    }
+
+    #[test]
+    fn cubic_spline_rejects_repeated_or_unsorted_points() {
+        assert!(FuzzySet::cubic_spline_interpolation("s", &[]).is_err());
+        assert!(FuzzySet::cubic_spline_interpolation("s", &[(0.0, 0.0), (0.0, 1.0)]).is_err());
+        assert!(FuzzySet::cubic_spline_interpolation("s", &[(1.0, 0.0), (0.0, 1.0)]).is_err());
+        assert!(FuzzySet::cubic_spline_interpolation("s", &[(0.0, 0.0), (1.0, 1.5)]).is_err());
+    }
+
+    #[test]
+    fn triangle_widths_match_its_geometry() {
+        let domain = Domain::new(-1.0, 11.0, 0.01).unwrap();
+        let set = FuzzySet::triangular("wide", 0.0, 5.0, 10.0);
+
+        assert!((set.pwhm(&domain) - 5.0).abs() < 1e-9);
+        assert!((set.support_width(&domain) - 10.0).abs() < 1e-9);
+        assert!((set.full_width_at_fraction_maximum(0.5, &domain) - set.pwhm(&domain)).abs() < 1e-12);
+        assert_eq!(set.full_width_at_fraction_maximum(0.0, &domain), set.support_width(&domain));
+    }
+
+    #[test]
+    fn widths_of_empty_set_are_zero() {
+        let empty = FuzzySet::new("empty", Arc::new(|_| 0.0));
+
+        assert_eq!(empty.pwhm(&unit_domain()), 0.0);
+        assert_eq!(empty.support_width(&unit_domain()), 0.0);
+    }
+
+    #[test]
+    fn compose_reproduces_standard_operations() {
+        let a = FuzzySet::triangular("a", 0.0, 0.4, 0.8);
+        let b = FuzzySet::gaussian("b", 0.6, 0.2);
+        let min = a.compose(&b, f64::min, "min");
+        let max = a.compose(&b, f64::max, "max");
+        let product = a.compose(&b, |x, y| x * y, "product");
+
+        for x in unit_domain().points() {
+            assert_eq!(min.membership_degree(x), a.intersection(&b).membership_degree(x));
+            assert_eq!(max.membership_degree(x), a.union(&b).membership_degree(x));
+            assert_eq!(product.membership_degree(x), a.intersection_product(&b).membership_degree(x));
+        }
+        assert_eq!(min.name(), "min");
+    }
+
+    #[test]
+    fn compose_accepts_custom_operators() {
+        let a = FuzzySet::triangular("a", 0.0, 0.4, 0.8);
+        let b = FuzzySet::gaussian("b", 0.6, 0.2);
+        let custom = a.compose(&b, |x, y| f64::max(0.0, x + y - 0.7), "custom");
+
+        for x in unit_domain().points() {
+            let expected = f64::max(0.0, a.membership_degree(x) + b.membership_degree(x) - 0.7);
+            assert_eq!(custom.membership_degree(x), expected);
+        }
+    }
+
+    #[test]
+    fn linear_transformer_doubles_numeric_output() {
+        let engine = priority_engine().with_output_transformer(Arc::new(|x| 2.0 * x));
+
+        for input in unit_domain().points() {
+            assert_eq!(engine.infer_numeric(input), 2.0 * priority_engine().infer_numeric(input));
+        }
+    }
+
+    #[test]
+    fn transformer_applies_before_label_mapping_and_in_traces() {
+        let engine = priority_engine().with_output_transformer(Arc::new(|x| 2.0 * x));
+        let trace = engine.trace(0.5);
+
+        assert_eq!(priority_engine().infer(0.5), "High Priority");
+        assert_eq!(engine.infer(0.5), "Urgent");
+        assert_eq!(trace.score, 2.0);
+        assert_eq!(trace.output_score, 4.0);
+        assert!(engine.explain(0.5).contains("transformed to 4.00"));
+    }
+
+    #[test]
+    fn transformer_is_not_applied_to_the_default() {
+        let engine = InferenceEngine::new(Vec::new())
+            .with_default_numeric(1.0)
+            .with_output_transformer(Arc::new(|x| 2.0 * x));
+
+        assert_eq!(engine.infer_numeric(0.5), 1.0);
+    }
+
+    #[test]
+    fn l2_distance_to_self_is_zero() {
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+
+        assert_eq!(set.l2_distance(&set, &unit_domain()), 0.0);
+        assert_eq!(set.l1_distance(&set, &unit_domain()), 0.0);
+    }
+
+    #[test]
+    fn l2_distance_satisfies_triangle_inequality() {
+        let domain = unit_domain();
+        let a = FuzzySet::triangular("a", 0.0, 0.3, 0.6);
+        let b = FuzzySet::triangular("b", 0.2, 0.5, 0.8);
+        let c = FuzzySet::triangular("c", 0.4, 0.7, 1.0);
+
+        assert!(a.l2_distance(&c, &domain) <= a.l2_distance(&b, &domain) + b.l2_distance(&c, &domain) + 1e-12);
+        assert!(a.l1_distance(&c, &domain) <= a.l1_distance(&b, &domain) + b.l1_distance(&c, &domain) + 1e-12);
+    }
+
+    #[test]
+    fn l1_and_l2_distances_are_related_by_cauchy_schwarz() {
+        let domain = Domain::new(0.0, 4.0, 0.01).unwrap();
+        let a = FuzzySet::triangular("a", 0.0, 1.0, 2.0);
+        let b = FuzzySet::gaussian("b", 2.0, 0.7);
+        let l1 = a.l1_distance(&b, &domain);
+        let l2 = a.l2_distance(&b, &domain);
+
+        assert!(l1 <= domain.width().sqrt() * l2 + 1e-12);
+        assert!(l2 * l2 <= l1 + 1e-12);
+    }
+
+    #[test]
+    fn consequent_fuzzy_sets_lists_only_fired_rules() {
+        let consequents = priority_engine().consequent_fuzzy_sets(0.8);
+        let summary: Vec<(&str, f64)> = consequents.iter().map(|(set, weight)| (set.name(), *weight)).collect();
+
+        assert_eq!(summary, vec![("Urgent", 1.0), ("High Priority", 0.5)]);
+        assert!(InferenceEngine::new(Vec::new()).consequent_fuzzy_sets(0.8).is_empty());
+    }
+
+    #[test]
+    fn weighted_aggregation_scales_then_unions() {
+        let consequents = priority_engine().consequent_fuzzy_sets(0.8);
+        let aggregate = InferenceEngine::aggregate_weighted_fuzzy_sets(&consequents);
+
+        for x in Domain::new(0.0, 1.5, 0.01).unwrap().points() {
+            let expected = consequents
+                .iter()
+                .map(|(set, weight)| set.membership_degree(x) * weight)
+                .fold(0.0, f64::max);
+            assert_eq!(aggregate.membership_degree(x), expected);
+        }
+    }
+
+    #[test]
+    fn make_convex_keeps_convex_sets() {
+        let domain = unit_domain();
+        let set = FuzzySet::gaussian("g", 0.5, 0.15);
+        let convex = set.make_convex(&domain);
+
+        for x in domain.points() {
+            assert!((convex.membership_degree(x) - set.membership_degree(x)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn make_convex_fills_in_bimodal_sets() {
+        let domain = unit_domain();
+        let left = FuzzySet::triangular("left", 0.0, 0.25, 0.5);
+        let right = FuzzySet::triangular("right", 0.5, 0.75, 1.0).scale(0.8);
+        let bimodal = left.union(&right);
+        let convex = bimodal.make_convex(&domain);
+
+        assert_eq!(bimodal.membership_degree(0.5), 0.0);
+        assert!((convex.membership_degree(0.5) - 0.8).abs() < 1e-12);
+        assert!(domain.points().all(|x| convex.membership_degree(x) >= bimodal.membership_degree(x)));
+        assert!((convex.membership_degree(0.9) - bimodal.membership_degree(0.9)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn moving_max_fills_a_dip_at_the_peak() {
+        let domain = unit_domain();
+        let triangle = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let peak = triangle.clone();
+        let dipped = FuzzySet::new(
+            "dipped",
+            Arc::new(move |x: f64| if (x - 0.5).abs() < 1e-9 { 0.7 } else { peak.membership_degree(x) }),
+        );
+        let smoothed = dipped.moving_max_smooth(3, &domain).unwrap();
+        let slope_step = domain.step() / 0.3;
+
+        for x in domain.points() {
+            assert!((smoothed.membership_degree(x) - triangle.membership_degree(x)).abs() <= slope_step + 1e-9);
+        }
+        assert!(smoothed.is_unimodal(&domain));
+    }
+
+    #[test]
+    fn moving_max_never_lowers_membership() {
+        let domain = unit_domain();
+        let set = jagged_set();
+        let smoothed = set.moving_max_smooth(4, &domain).unwrap();
+
+        assert!(domain.points().all(|x| smoothed.membership_degree(x) >= set.membership_degree(x) - 1e-12));
+        assert!(set.moving_max_smooth(0, &domain).is_err());
+    }
+
+    fn load_variable() -> LinguisticVariable {
+        LinguisticVariable::new(
+            "load",
+            vec![
+                FuzzySet::triangular("low", -0.5, 0.0, 0.5),
+                FuzzySet::triangular("mid", 0.0, 0.5, 1.0),
+                FuzzySet::triangular("high", 0.5, 1.0, 1.5),
+            ],
+        )
+    }
+
+    fn priority_sets() -> [FuzzySet; 3] {
+        [
+            FuzzySet::triangular("Medium Priority", 0.1, 0.4, 0.7),
+            FuzzySet::triangular("High Priority", 0.4, 0.7, 1.0),
+            FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3),
+        ]
+    }
+
+    #[test]
+    fn csv_training_data_builds_a_classifier() {
+        let csv = "load,label\n\
+                   0.0,Medium Priority\n0.1,Medium Priority\n0.2,Medium Priority\n\
+                   0.4,High Priority\n0.5,High Priority\n0.6,High Priority\n\
+                   0.8,Urgent\n0.9,Urgent\n1.0,Urgent\n";
+        let [medium, high, urgent] = priority_sets();
+        let engine =
+            InferenceEngine::from_csv_training_data(csv, &load_variable(), &[&medium, &high, &urgent]).unwrap();
+
+        assert_eq!(engine.rule_count(), 3);
+        assert!(engine.get_rule_weights().iter().all(|&weight| (weight - 1.0 / 3.0).abs() < 1e-12));
+        for line in csv.lines().skip(1) {
+            let (input, label) = line.split_once(',').unwrap();
+            assert_eq!(engine.infer(input.parse().unwrap()), label);
+        }
+    }
+
+    #[test]
+    fn csv_rule_weights_follow_label_frequency() {
+        let csv = "0.0,Medium Priority\n0.1,Medium Priority\n0.1,High Priority\n0.9,Urgent\n";
+        let [medium, high, urgent] = priority_sets();
+        let engine =
+            InferenceEngine::from_csv_training_data(csv, &load_variable(), &[&medium, &high, &urgent]).unwrap();
+
+        assert_eq!(engine.get_rule_weights(), vec![0.5, 0.25, 0.25]);
+        assert_eq!(engine.rules_for_consequence("Medium Priority")[0].name(), Some("load is low -> Medium Priority"));
+    }
+
+    #[test]
+    fn csv_training_data_reports_bad_rows() {
+        let [medium, high, urgent] = priority_sets();
+        let outputs = [&medium, &high, &urgent];
+
+        assert!(matches!(
+            InferenceEngine::from_csv_training_data("0.1,Medium Priority\nx,Urgent\n", &load_variable(), &outputs),
+            Err(FuzzyError::InvalidCsv { line: 2, .. })
+        ));
+        assert!(matches!(
+            InferenceEngine::from_csv_training_data("0.1\n", &load_variable(), &outputs),
+            Err(FuzzyError::InvalidCsv { line: 1, .. })
+        ));
+        assert!(matches!(
+            InferenceEngine::from_csv_training_data("0.1,Unknown\n", &load_variable(), &outputs),
+            Err(FuzzyError::UnknownConsequence(_))
+        ));
+    }
+
+    #[test]
+    fn alpha_cut_svg_is_well_formed() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let svg = set.alpha_cut_svg(&[0.0, 0.5, 1.0, 1.5], &domain, 200, 100);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches('<').count(), svg.matches('>').count());
+        assert_eq!(svg.matches("<line ").count(), 3);
+        assert_eq!(svg.matches("<line ").count(), svg.matches(" />").count());
+    }
+
+    #[test]
+    fn alpha_cut_series_narrows_and_starts_at_support() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let series = set.alpha_cut_series(&[0.0, 0.25, 0.5, 0.75, 1.0], &domain);
+        let intervals: Vec<(f64, f64)> = series.iter().map(|(_, interval)| interval.unwrap()).collect();
+        let support = set.support(&domain);
+
+        assert_eq!(intervals[0], (support[0], support[support.len() - 1]));
+        assert!(intervals.windows(2).all(|pair| pair[1].1 - pair[1].0 <= pair[0].1 - pair[0].0));
+    }
+
+    #[test]
+    fn universal_set_has_zero_specificity() {
+        let universal = FuzzySet::new("universal", Arc::new(|_| 1.0));
+
+        assert!(universal.specificity(&unit_domain()).abs() < 1e-12);
+        assert!(universal.information_content_bits(&unit_domain()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn singleton_is_more_specific_than_any_wider_set() {
+        let domain = unit_domain();
+        let singleton = FuzzySet::new("point", Arc::new(|x: f64| if (x - 0.5).abs() < 1e-9 { 1.0 } else { 0.0 }));
+        let narrow = FuzzySet::triangular("narrow", 0.45, 0.5, 0.55);
+        let wide = FuzzySet::triangular("wide", 0.0, 0.5, 1.0);
+        let specificities = [&singleton, &narrow, &wide].map(|set| set.specificity(&domain));
+
+        assert!((specificities[0] - (1.0 - domain.step() / domain.width())).abs() < 1e-12);
+        assert!(specificities[0] > specificities[1] && specificities[1] > specificities[2]);
+        assert!(specificities.iter().all(|s| (0.0..=1.0).contains(s)));
+        assert!(singleton.information_content_bits(&domain) > wide.information_content_bits(&domain));
+        assert!((wide.information_content_bits(&domain) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn lookup_table_matches_inference_within_interpolation_error() {
+        let engine = graded_engine(1.0, 0.3);
+        let table = engine.to_lookup_table(&unit_domain());
+
+        assert_eq!(table.outputs().len(), unit_domain().sample_count());
+        for x in unit_domain().points() {
+            assert!((table.query(x) - engine.infer_numeric(x)).abs() < 1e-12);
+        }
+        for i in 0..100 {
+            let x = i as f64 / 100.0 + 0.005;
+            assert!((table.query(x) - engine.infer_numeric(x)).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn lookup_table_queries_do_not_reevaluate_rules() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let engine = InferenceEngine::new(vec![FuzzyRule::new_graded(
+            Box::new(move |x| {
+                counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                x.clamp(0.0, 1.0)
+            }),
+            FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3),
+            1.0,
+        )]);
+        let table = engine.to_lookup_table(&unit_domain());
+        let built = calls.load(std::sync::atomic::Ordering::Relaxed);
+
+        for i in 0..1000 {
+            table.query(i as f64 / 1000.0);
+        }
+        assert_eq!(calls.load(std::sync::atomic::Ordering::Relaxed), built);
+    }
+
+    #[test]
+    fn lookup_table_clamps_queries_to_its_domain() {
+        let engine = graded_engine(1.0, 0.3);
+        let table = engine.to_lookup_table(&unit_domain());
+
+        assert_eq!(table.query(-5.0), table.query(0.0));
+        assert_eq!(table.query(5.0), table.query(1.0));
+        assert_eq!(table.domain(), &unit_domain());
+    }
+
+    #[test]
+    fn fuzzify_aliases_membership_degree() {
+        let set = FuzzySet::gaussian("g", 0.5, 0.2);
+
+        for x in unit_domain().points() {
+            assert_eq!(set.fuzzify(x), set.membership_degree(x));
+        }
+    }
+
+    #[test]
+    fn fuzzify_all_reports_every_term() {
+        let variable = load_variable();
+        let degrees = variable.fuzzify_all(0.25);
+
+        assert_eq!(degrees.len(), 3);
+        for term in variable.terms() {
+            assert_eq!(degrees[term.name()], term.membership_degree(0.25));
+        }
+        assert_eq!(variable.best_term(0.9).map(FuzzySet::name), Some("high"));
+    }
+
+    #[test]
+    fn every_defuzzification_method_is_sensible_on_a_triangle() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let methods = [
+            DefuzzMethod::Centroid,
+            DefuzzMethod::MeanOfMaximum,
+            DefuzzMethod::Bisector,
+            DefuzzMethod::FirstOfMaximum,
+            DefuzzMethod::LastOfMaximum,
+            DefuzzMethod::Percentile(0.5),
+        ];
+
+        for method in methods {
+            assert!((set.defuzzify(method, &domain).unwrap() - 0.5).abs() < 1e-9);
+        }
+        assert!(set.defuzzify(DefuzzMethod::Percentile(0.1), &domain).unwrap() < 0.5);
+        assert_eq!(set.defuzzify(DefuzzMethod::Centroid, &domain).unwrap(), set.centroid(&domain));
+    }
+
+    #[test]
+    fn defuzzify_reports_empty_sets_and_bad_percentiles() {
+        let domain = unit_domain();
+        let empty = FuzzySet::new("empty", Arc::new(|_| 0.0));
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+
+        assert_eq!(empty.defuzzify(DefuzzMethod::Centroid, &domain), Err(FuzzyError::EmptySupport));
+        assert_eq!(empty.defuzzify(DefuzzMethod::MeanOfMaximum, &domain), Err(FuzzyError::EmptySupport));
+        assert_eq!(set.defuzzify(DefuzzMethod::Percentile(2.0), &domain), Err(FuzzyError::InvalidPercentile(2.0)));
+    }
+
+    #[test]
+    fn bimodal_union_has_two_local_maxima() {
+        let domain = unit_domain();
+        let left = FuzzySet::triangular("left", 0.0, 0.2, 0.4);
+        let right = FuzzySet::triangular("right", 0.6, 0.8, 1.0);
+        let maxima = left.union(&right).local_maxima(&domain);
+
+        assert_eq!(maxima.len(), 2);
+        assert!((maxima[0].0 - 0.2).abs() < 1e-9 && maxima[0].1 == 1.0);
+        assert!((maxima[1].0 - 0.8).abs() < 1e-9 && maxima[1].1 == 1.0);
+        assert!(!left.union(&right).is_unimodal(&domain));
+    }
+
+    #[test]
+    fn plateau_counts_as_one_maximum() {
+        let domain = unit_domain();
+        let set = FuzzySet::trapezoidal("plateau", 0.1, 0.3, 0.7, 0.9);
+        let maxima = set.local_maxima(&domain);
+
+        assert_eq!(maxima.len(), 1);
+        assert!((maxima[0].0 - 0.5).abs() < 1e-9);
+        assert!(set.is_unimodal(&domain));
+        assert!(FuzzySet::new("empty", Arc::new(|_| 0.0)).local_maxima(&domain).is_empty());
+    }
+
+    #[test]
+    fn aggregated_defuzzify_matches_manual_mamdani_steps() {
+        let domain = Domain::new(0.0, 1.5, 0.01).unwrap();
+        let urgent = FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3);
+        let high = FuzzySet::triangular("High Priority", 0.4, 0.7, 1.0);
+        let sets = [(&urgent, 0.6), (&high, 0.3)];
+
+        let manual = urgent.clip(0.6).union(&high.clip(0.3)).centroid(&domain);
+        assert_eq!(FuzzySet::aggregated_defuzzify(&sets, DefuzzMethod::Centroid, &domain), Ok(manual));
+
+        let engine = InferenceEngine::new(vec![
+            FuzzyRule::new(Box::new(|_| true), urgent.clone(), 0.6),
+            FuzzyRule::new(Box::new(|_| true), high.clone(), 0.3),
+        ]);
+        assert_eq!(
+            FuzzySet::aggregated_defuzzify(&sets, DefuzzMethod::MeanOfMaximum, &domain),
+            engine.output_fuzzy_set(0.0).defuzzify(DefuzzMethod::MeanOfMaximum, &domain)
+        );
+    }
+
+    #[test]
+    fn aggregated_defuzzify_of_nothing_is_empty() {
+        assert_eq!(
+            FuzzySet::aggregated_defuzzify(&[], DefuzzMethod::Centroid, &unit_domain()),
+            Err(FuzzyError::EmptySupport)
+        );
+    }
+
+    #[test]
+    fn batch_evaluation_matches_pointwise_evaluation() {
+        let set = FuzzySet::gaussian("g", 0.5, 0.2);
+        let xs: Vec<f64> = (0..100_000).map(|i| i as f64 / 100_000.0).collect();
+        let pointwise: Vec<f64> = xs.iter().map(|&x| set.membership_degree(x)).collect();
+
+        assert_eq!(set.membership_degree_batch(&xs), pointwise);
+        assert_eq!(set.membership_degree_par_batch(&xs), pointwise);
+    }
+
+    #[test]
+    fn batch_evaluation_handles_empty_and_tiny_inputs() {
+        let set = FuzzySet::gaussian("g", 0.5, 0.2);
+
+        assert!(set.membership_degree_batch(&[]).is_empty());
+        assert!(set.membership_degree_par_batch(&[]).is_empty());
+        assert_eq!(set.membership_degree_par_batch(&[0.5]), vec![1.0]);
+    }
+
+    #[test]
+    fn count_where_positive_membership_is_support_size() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+
+        assert_eq!(set.count_where(|_, mu| mu > 0.0, &domain), set.support(&domain).len());
+        assert_eq!(set.count_where(|_, mu| mu >= 0.5, &domain), set.alpha_cut(0.5, &domain).len());
+    }
+
+    #[test]
+    fn fraction_where_is_a_fraction_of_all_samples() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+
+        for alpha in [0.0, 0.25, 0.5, 1.0, 2.0] {
+            assert!((0.0..=1.0).contains(&set.fraction_where(|_, mu| mu >= alpha, &domain)));
+        }
+        assert_eq!(set.fraction_where(|_, _| true, &domain), 1.0);
+        assert_eq!(set.fraction_where(|x, _| x < 0.5, &domain), 50.0 / 101.0);
+    }
+
+    #[test]
+    fn priority_histogram_counts_every_input() {
+        let engine = priority_engine();
+        let inputs: Vec<f64> = unit_domain().points().collect();
+        let histogram = engine.priority_histogram(&inputs);
+        let distribution = engine.priority_distribution(&inputs);
+
+        assert_eq!(histogram.values().sum::<usize>(), inputs.len());
+        assert!((distribution.values().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert_eq!(histogram["Medium Priority"], 41);
+    }
+
+    #[test]
+    fn urgent_only_rule_base_is_all_urgent() {
+        let engine = InferenceEngine::new(vec![FuzzyRule::new(
+            Box::new(|_| true),
+            FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3),
+            1.0,
+        )]);
+        let inputs = [0.0, 0.3, 0.6, 0.9];
+
+        assert_eq!(engine.priority_histogram(&inputs), HashMap::from([("Urgent".to_string(), 4)]));
+        assert_eq!(engine.priority_distribution(&inputs), HashMap::from([("Urgent".to_string(), 1.0)]));
+    }
+
+    #[test]
+    fn union_then_normalize_lifts_subnormal_sets_to_height_one() {
+        let domain = unit_domain();
+        let left = FuzzySet::triangular("left", 0.0, 0.25, 0.5).scale(0.4);
+        let right = FuzzySet::triangular("right", 0.5, 0.75, 1.0).scale(0.6);
+        let normalized = FuzzySet::union_then_normalize("both", &[&left, &right], &domain);
+
+        assert!((normalized.height(&domain) - 1.0).abs() < 1e-12);
+        assert!((normalized.membership_degree(0.25) - 0.4 / 0.6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn union_then_normalize_leaves_empty_union_empty() {
+        let empty = FuzzySet::new("empty", Arc::new(|_| 0.0));
+        let normalized = FuzzySet::union_then_normalize("none", &[&empty], &unit_domain());
+
+        assert_eq!(normalized.height(&unit_domain()), 0.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn explain_json_reports_trace_fields() {
+        let engine = priority_engine();
+        let value: serde_json::Value = serde_json::from_str(&engine.explain_json(0.8).unwrap()).unwrap();
+        let fired = value["fired_rules"].as_array().unwrap();
+
+        assert_eq!(value["input"].as_f64(), Some(0.8));
+        assert_eq!(value["output"].as_str(), Some("Urgent"));
+        assert_eq!(value["numeric_score"].as_f64(), Some(engine.infer_numeric(0.8)));
+        assert_eq!(value["raw_score"].as_f64(), Some(engine.trace(0.8).score));
+        assert_eq!(value["total_weight"].as_f64(), Some(engine.trace(0.8).total_weight));
+        assert_eq!(fired.len(), 2);
+        assert_eq!(fired[0]["name"].as_str(), Some("very high"));
+        assert_eq!(fired[0]["consequence"].as_str(), Some("Urgent"));
+        assert_eq!(fired[1]["index"].as_u64(), Some(1));
+        assert_eq!(value["weights"]["high"].as_f64(), Some(0.5));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn explain_json_uses_index_for_unnamed_rules_and_null_names() {
+        let engine = InferenceEngine::new(vec![FuzzyRule::new(
+            Box::new(|_| true),
+            FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3),
+            1.0,
+        )]);
+        let value: serde_json::Value = serde_json::from_str(&engine.explain_json(0.5).unwrap()).unwrap();
+
+        assert!(value["fired_rules"][0]["name"].is_null());
+        assert_eq!(value["weights"]["0"].as_f64(), Some(1.0));
+    }
+
+    #[test]
+    fn volume_matches_scalar_cardinality() {
+        let domain = Domain::new(0.0, 1.0, 0.001).unwrap();
+        let sets = [
+            FuzzySet::triangular("t", 0.2, 0.5, 0.8),
+            FuzzySet::gaussian("g", 0.5, 0.1),
+            FuzzySet::trapezoidal("z", 0.1, 0.3, 0.6, 0.9),
+        ];
+
+        for set in &sets {
+            let cardinality = set.scalar_cardinality(&domain);
+            assert!((set.volume(&domain, 0.01) - cardinality).abs() < 0.01 * cardinality);
+        }
+    }
+
+    #[test]
+    fn volume_needs_a_positive_alpha_step() {
+        let set = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+
+        assert_eq!(set.volume(&unit_domain(), 0.0), 0.0);
+        assert_eq!(set.area_above_alpha(0.0, &unit_domain()), set.support_width(&unit_domain()));
+    }
+
+    #[test]
+    fn narrow_gaussian_has_smaller_variance() {
+        let domain = unit_domain();
+        let narrow = FuzzySet::gaussian("narrow", 0.5, 0.05);
+        let wide = FuzzySet::gaussian("wide", 0.5, 0.15);
+
+        assert!(narrow.membership_variance(&domain) < wide.membership_variance(&domain));
+        assert!((narrow.membership_std_dev(&domain) - 0.05).abs() < 1e-6);
+    }
+
+    #[test]
+    fn symmetric_triangle_variance_matches_closed_form() {
+        let domain = Domain::new(0.0, 1.0, 0.001).unwrap();
+        let set = FuzzySet::triangular("mid", 0.2, 0.5, 0.8);
+        let expected = (0.8f64 - 0.2).powi(2) / 24.0;
+
+        assert!((set.membership_variance(&domain) - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn unfired_rules_have_zero_weight_sensitivity() {
+        let sensitivity = priority_engine().rule_weight_sensitivity(0.8, 0.1);
+
+        assert_eq!(sensitivity.len(), 3);
+        assert_eq!(sensitivity[2], 0.0);
+    }
+
+    #[test]
+    fn dominant_fired_rule_has_positive_weight_sensitivity() {
+        let sensitivity = priority_engine().rule_weight_sensitivity(0.8, 0.1);
+
+        assert!(sensitivity[0] > 0.0);
+        assert!(sensitivity[1] > 0.0);
+    }
+
+    #[test]
+    fn rank_centroid_orders_shifted_triangles() {
+        let domain = Domain::new(0.0, 5.0, 0.01).unwrap();
+        let lower = FuzzySet::triangular("lower", 0.0, 2.0, 4.0);
+        let upper = FuzzySet::triangular("upper", 1.0, 3.0, 5.0);
+
+        assert_eq!(lower.rank_centroid(&upper, &domain), Ordering::Less);
+        assert_eq!(upper.rank_centroid(&lower, &domain), Ordering::Greater);
+        assert_eq!(lower.rank_centroid(&lower, &domain), Ordering::Equal);
+    }
+
+    #[test]
+    fn rank_defuzz_agrees_with_rank_centroid() {
+        let domain = Domain::new(0.0, 5.0, 0.01).unwrap();
+        let lower = FuzzySet::triangular("lower", 0.0, 2.0, 4.0);
+        let upper = FuzzySet::triangular("upper", 1.0, 3.0, 5.0);
+
+        assert_eq!(
+            lower.rank_defuzz(&upper, DefuzzMethod::Centroid, &domain).ok(),
+            Some(lower.rank_centroid(&upper, &domain))
+        );
+        assert_eq!(
+            upper.rank_defuzz(&lower, DefuzzMethod::MeanOfMaximum, &domain).ok(),
+            Some(Ordering::Greater)
+        );
+    }
+
+    #[test]
+    fn ascii_plot_has_requested_dimensions() {
+        let plot = FuzzySet::triangular("t", 0.2, 0.5, 0.8).ascii_plot(&unit_domain(), 11, 5);
+        let lines: Vec<&str> = plot.lines().collect();
+
+        assert_eq!(lines.len(), 6);
+        assert!(lines.iter().all(|line| line.chars().count() == 11));
+        assert_eq!(lines[5], "0         1");
+    }
+
+    #[test]
+    fn ascii_plot_marks_the_peak_column() {
+        let plot = FuzzySet::triangular("t", 0.2, 0.5, 0.8).ascii_plot(&unit_domain(), 11, 5);
+        let top = plot.lines().next().unwrap();
+
+        assert_eq!(top.find('*'), Some(5));
+        assert_eq!(top.matches('*').count(), 1);
+        assert!(plot.contains('*'));
+    }
+
+    #[test]
+    fn clip_domain_keeps_the_rising_slope() {
+        let set = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+        let clipped = set.clip_domain(0.2, 0.5).unwrap();
+
+        for x in [0.25, 0.3, 0.4, 0.5] {
+            assert_eq!(clipped.membership_degree(x), set.membership_degree(x));
+        }
+        for x in [0.55, 0.6, 0.7] {
+            assert_eq!(clipped.membership_degree(x), 0.0);
+        }
+    }
+
+    #[test]
+    fn clip_domain_rejects_empty_interval() {
+        let set = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+
+        assert!(matches!(
+            set.clip_domain(0.5, 0.5),
+            Err(FuzzyError::InvalidInterval { .. })
+        ));
+        assert!(set.clip_domain(0.6, 0.1).is_err());
+    }
+
+    #[test]
+    fn extend_domain_zeros_outside_original_domain() {
+        let original = unit_domain();
+        let set = FuzzySet::sigmoidal("s", 10.0, 0.5);
+        let extended = set.extend_domain(&original);
+
+        for x in [-2.0, -0.01, 1.01, 3.0] {
+            assert_eq!(extended.membership_degree(x), 0.0);
+        }
+        for x in original.points() {
+            assert_eq!(extended.membership_degree(x), set.membership_degree(x));
+        }
+    }
+
+    #[test]
+    fn triangular_set_is_bounded_by_its_feet() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+
+        assert!(set.is_bounded(0.2, 0.8, &domain));
+        assert!(!set.is_bounded(0.3, 0.8, &domain));
+        assert!(!FuzzySet::gaussian("g", 0.5, 0.1).is_bounded(0.2, 0.8, &domain));
+    }
+
+    #[test]
+    fn tight_bounds_lie_within_the_support() {
+        let (left, right) = FuzzySet::triangular("t", 0.2, 0.5, 0.8)
+            .tight_bounds(&unit_domain(), 0.01)
+            .unwrap();
+
+        assert!((0.2..=0.22).contains(&left));
+        assert!((0.78..=0.8).contains(&right));
+        assert_eq!(FuzzySet::triangular("t", 0.2, 0.5, 0.8).tight_bounds(&unit_domain(), 1.1), None);
+    }
+
+    #[test]
+    fn reconstruction_error_of_grid_aligned_triangle_is_within_step() {
+        let domain = unit_domain();
+        let (reconstructed, max_error) =
+            FuzzySet::triangular("t", 0.2, 0.5, 0.8).discretize_and_reconstruct(&domain);
+
+        assert!(max_error <= domain.step());
+        assert!(max_error < 1e-9);
+        assert!((reconstructed.membership_degree(0.35) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reconstruction_error_of_smooth_set_is_within_step() {
+        let domain = unit_domain();
+        let (_, max_error) = FuzzySet::gaussian("g", 0.5, 0.1).discretize_and_reconstruct(&domain);
+
+        assert!(max_error > 0.0);
+        assert!(max_error <= domain.step());
+    }
+
+    #[test]
+    fn uniform_weights_match_equal_weights() {
+        let mut uniform = priority_engine();
+        uniform.reset_weights_to_uniform();
+        let mut equal = priority_engine();
+        equal.reset_weights(&[1.0, 1.0, 1.0]).unwrap();
+
+        for x in [0.2, 0.5, 0.8, 0.95] {
+            assert!((uniform.infer_numeric(x) - equal.infer_numeric(x)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn reset_weights_validates_input() {
+        let mut engine = priority_engine();
+
+        assert!(matches!(
+            engine.reset_weights(&[1.0, 1.0]),
+            Err(FuzzyError::WeightCountMismatch { expected: 3, actual: 2 })
+        ));
+        assert!(engine.reset_weights(&[1.0, -0.5, 1.0]).is_err());
+    }
+
+    #[test]
+    fn retuning_after_uniform_reset_reduces_loss() {
+        let data = graded_samples(1.0, 0.3);
+        let mut engine = graded_engine(1.0, 1.0);
+        let original = engine.tune_weights(&data, 0.5, 50);
+        engine.reset_weights_to_uniform();
+        let retuned = engine.tune_weights(&data, 0.5, 50);
+
+        assert!((retuned[0] - original[0]).abs() < 1e-12);
+        assert!(retuned.last().unwrap() <= &original[0]);
+    }
+
+    #[test]
+    fn map_domain_generalises_shift_and_scale() {
+        let set = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+        let shifted = set.map_domain(|x| x - 1.0, "shifted");
+        let scaled = set.map_domain(|x| x / 2.0, "scaled");
+
+        for x in Domain::new(0.0, 2.0, 0.05).unwrap().points() {
+            assert_eq!(shifted.membership_degree(x), set.shift(1.0).membership_degree(x));
+            assert_eq!(scaled.membership_degree(x), set.scale_domain(2.0).membership_degree(x));
+        }
+    }
+
+    #[test]
+    fn map_domain_checked_rejects_non_monotone_maps() {
+        let set = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+        let domain = unit_domain();
+
+        assert!(set.map_domain_checked(|x| x.ln_1p(), "log", &domain).is_ok());
+        assert!(set.map_domain_checked(|x| -x, "mirror", &domain).is_ok());
+        assert!(matches!(
+            set.map_domain_checked(|x| (x - 0.5).abs(), "fold", &domain),
+            Err(FuzzyError::NonMonotoneMapping { .. })
+        ));
+        assert!(set.map_domain_checked(|_| 0.5, "constant", &domain).is_err());
+    }
+
+    #[test]
+    fn triangular_set_has_one_interval_per_alpha() {
+        let cuts = FuzzySet::triangular("t", 0.2, 0.5, 0.8).to_crisp_intervals(&[0.25, 0.5, 0.75], &unit_domain());
+
+        assert_eq!(cuts.len(), 3);
+        for (alpha, intervals) in &cuts {
+            let half_width = 0.3 * (1.0 - alpha);
+            assert_eq!(intervals.len(), 1);
+            assert!((intervals[0].0 - (0.5 - half_width)).abs() < 0.011);
+            assert!((intervals[0].1 - (0.5 + half_width)).abs() < 0.011);
+        }
+    }
+
+    #[test]
+    fn disjoint_union_has_two_intervals_below_the_peaks() {
+        let set = FuzzySet::triangular("a", 0.1, 0.2, 0.3).union(&FuzzySet::triangular("b", 0.6, 0.7, 0.8));
+        let cuts = set.to_crisp_intervals(&[0.5, 1.1], &unit_domain());
+
+        assert_eq!(cuts[0].1.len(), 2);
+        assert!(cuts[0].1[0].1 < cuts[0].1[1].0);
+        assert!(cuts[1].1.is_empty());
+    }
+
+    fn lcg(seed: u64) -> impl FnMut() -> f64 {
+        let mut state = seed;
+
+        move || {
+            state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        }
+    }
+
+    #[test]
+    fn probabilistic_infer_of_crisp_rules_is_deterministic() {
+        let engine = priority_engine();
+
+        for x in [0.2, 0.5, 0.8] {
+            let distribution = engine.probabilistic_infer(x, &mut lcg(7), 100);
+            assert_eq!(distribution.len(), 1);
+            assert_eq!(distribution.get(&engine.infer(x)), Some(&1.0));
+        }
+    }
+
+    #[test]
+    fn probabilistic_infer_converges_to_firing_probabilities() {
+        let engine = graded_engine(1.0, 1.0);
+        let distribution = engine.probabilistic_infer(0.9, &mut lcg(7), 100_000);
+        let expected = [("Urgent", 0.81), ("High Priority", 0.09), ("Low Priority", 0.09), ("Medium Priority", 0.01)];
+
+        for (label, probability) in expected {
+            assert!((distribution[label] - probability).abs() < 0.01);
+        }
+        let mode = distribution
+            .iter()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+            .map(|(label, _)| label.clone());
+        assert_eq!(mode, Some(engine.infer(0.9)));
+    }
+
+    #[test]
+    fn kde_peaks_near_sample_mean() {
+        let mut uniform = lcg(3);
+        let samples: Vec<f64> = (0..200).map(|_| 0.5 + 0.1 * standard_normal(&mut uniform)).collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let set = FuzzySet::from_kde("kde", &samples, 0.05);
+        let mode = set.defuzzify(DefuzzMethod::MeanOfMaximum, &unit_domain()).unwrap();
+
+        assert!((mode - mean).abs() < 0.05);
+        assert!((set.height(&unit_domain()) - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn smaller_bandwidth_gives_narrower_kde() {
+        let samples = [0.4, 0.45, 0.5, 0.55, 0.6];
+        let domain = unit_domain();
+        let narrow = FuzzySet::from_kde("narrow", &samples, 0.02);
+        let wide = FuzzySet::from_kde("wide", &samples, 0.1);
+
+        assert!(narrow.membership_degree(0.9) < wide.membership_degree(0.9));
+        assert!(narrow.scalar_cardinality(&domain) < wide.scalar_cardinality(&domain));
+    }
+
+    #[test]
+    fn fuzzy_c_means_separates_bimodal_data() {
+        let data = [0.1, 0.12, 0.14, 0.16, 0.18, 0.8, 0.82, 0.84, 0.86, 0.88];
+        let clusters = FuzzySet::fuzzy_c_means(&data, 2, 2.0, 100, 1e-9).unwrap();
+
+        assert_eq!(clusters.len(), 2);
+        assert!(clusters[0].membership_degree(0.14) > 0.95);
+        assert!(clusters[1].membership_degree(0.84) > 0.95);
+        assert!(clusters[0].membership_degree(0.84) < 0.05);
+        for x in data {
+            let total: f64 = clusters.iter().map(|cluster| cluster.membership_degree(x)).sum();
+            assert!((total - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn fuzzy_c_means_validates_parameters() {
+        let data = [0.1, 0.2, 0.8];
+
+        assert!(FuzzySet::fuzzy_c_means(&data, 0, 2.0, 10, 1e-6).is_err());
+        assert!(FuzzySet::fuzzy_c_means(&data, 4, 2.0, 10, 1e-6).is_err());
+        assert!(FuzzySet::fuzzy_c_means(&data, 2, 1.0, 10, 1e-6).is_err());
+    }
+
+    #[test]
+    fn type_reduced_centroid_is_an_ordered_interval_in_the_domain() {
+        let domain = unit_domain();
+        let upper = FuzzySet::triangular("upper", 0.2, 0.5, 0.8);
+        let lower = FuzzySet::triangular("lower", 0.3, 0.5, 0.7).clip(0.6);
+        let set = Type2FuzzySet::new("fou", lower, upper, &domain).unwrap();
+        let (left, right) = set.centroid_type_reduction(&domain);
+
+        assert!(left <= right);
+        assert!(domain.min() <= left && right <= domain.max());
+        assert!(left < 0.5 && 0.5 < right);
+        assert!(((0.5 - left) - (right - 0.5)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn type2_set_rejects_crossing_footprint() {
+        let domain = unit_domain();
+        let result = Type2FuzzySet::new(
+            "crossed",
+            FuzzySet::triangular("wide", 0.1, 0.5, 0.9),
+            FuzzySet::triangular("narrow", 0.3, 0.5, 0.7),
+            &domain,
+        );
+
+        assert!(matches!(result.err(), Some(FuzzyError::InvalidFootprint { .. })));
+    }
+
+    #[test]
+    fn gaussian_is_symmetric_with_zero_skewness() {
+        let set = FuzzySet::gaussian("g", 0.5, 0.1);
+        let domain = unit_domain();
+
+        assert!((set.symmetry_index(&domain) - 1.0).abs() < 1e-6);
+        assert!(set.skewness(&domain).abs() < 1e-6);
+    }
+
+    #[test]
+    fn right_skewed_triangle_has_positive_skewness() {
+        let set = FuzzySet::triangular("skewed", 0.0, 0.1, 1.0);
+        let domain = unit_domain();
+
+        assert!(set.skewness(&domain) > 0.1);
+        assert!(set.symmetry_index(&domain) < 0.5);
+        assert!(FuzzySet::triangular("mirrored", 0.0, 0.9, 1.0).skewness(&domain) < -0.1);
+    }
+
+    #[test]
+    fn truth_table_generates_one_rule_per_term() {
+        let [medium, high, urgent] = priority_sets();
+        let mut engine = InferenceEngine::new(Vec::new());
+        engine
+            .add_rules_from_truth_table(
+                &load_variable(),
+                &[&medium, &high, &urgent],
+                &["Medium Priority", "High Priority", "Urgent"],
+            )
+            .unwrap();
+
+        assert_eq!(engine.rule_count(), 3);
+        assert_eq!(engine.infer(0.0), "Medium Priority");
+        assert_eq!(engine.infer(0.5), "High Priority");
+        assert_eq!(engine.infer(1.0), "Urgent");
+    }
+
+    #[test]
+    fn truth_table_must_cover_every_term() {
+        let [medium, high, urgent] = priority_sets();
+        let mut engine = InferenceEngine::new(Vec::new());
+
+        assert!(matches!(
+            engine.add_rules_from_truth_table(&load_variable(), &[&medium, &high, &urgent], &["Urgent"]),
+            Err(FuzzyError::TruthTableSize { expected: 3, actual: 1 })
+        ));
+        assert!(matches!(
+            engine.add_rules_from_truth_table(&load_variable(), &[&medium], &["Medium Priority", "Urgent", "Urgent"]),
+            Err(FuzzyError::UnknownConsequence(label)) if label == "Urgent"
+        ));
+        assert_eq!(engine.rule_count(), 0);
+    }
+
+    #[test]
+    fn gaussian_has_zero_excess_kurtosis() {
+        let domain = Domain::new(0.0, 1.0, 0.001).unwrap();
+
+        assert!(FuzzySet::gaussian("g", 0.5, 0.1).excess_kurtosis(&domain).abs() < 1e-3);
+    }
+
+    #[test]
+    fn excess_kurtosis_separates_light_and_heavy_tails() {
+        let domain = Domain::new(0.0, 1.0, 0.001).unwrap();
+        let triangle = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+        let laplacian = FuzzySet::new("laplace", Arc::new(|x: f64| (-(x - 0.5).abs() / 0.05).exp()));
+        let wide = Domain::new(-0.5, 1.5, 0.001).unwrap();
+
+        assert!((triangle.excess_kurtosis(&domain) + 0.6).abs() < 1e-2);
+        assert!((laplacian.excess_kurtosis(&wide) - 3.0).abs() < 0.1);
+        assert_eq!(triangle.kurtosis(&domain) - 3.0, triangle.excess_kurtosis(&domain));
+    }
+
+    #[test]
+    fn online_updates_move_output_towards_target() {
+        let target = graded_engine(1.0, 0.3).infer_numeric(0.5);
+        let mut engine = graded_engine(1.0, 1.0);
+        let initial_error = (engine.infer_numeric(0.5) - target).abs();
+
+        for _ in 0..100 {
+            engine.online_update(0.5, target, 0.5);
+        }
+
+        assert!((engine.infer_numeric(0.5) - target).abs() < initial_error);
+    }
+
+    #[test]
+    fn online_updates_converge_faster_per_epoch_than_batch_descent() {
+        let data = graded_samples(1.0, 0.3);
+        let loss = |engine: &InferenceEngine| {
+            data.iter()
+                .map(|&(input, target)| (engine.infer_numeric(input) - target).powi(2))
+                .sum::<f64>()
+                / data.len() as f64
+        };
+        let mut online = graded_engine(1.0, 1.0);
+        let mut batch = graded_engine(1.0, 1.0);
+        let initial = loss(&batch);
+        batch.tune_weights(&data, 0.5, 5);
+
+        for _ in 0..5 {
+            for &(input, target) in &data {
+                online.online_update(input, target, 0.5);
+            }
+        }
+
+        assert!(loss(&batch) < initial);
+        assert!(loss(&online) < loss(&batch));
+    }
+
+    #[test]
+    fn pdf_integrates_to_one() {
+        let domain = Domain::new(0.0, 1.0, 0.001).unwrap();
+
+        for set in [FuzzySet::triangular("t", 0.2, 0.5, 0.8), FuzzySet::gaussian("g", 0.4, 0.1)] {
+            assert!((set.to_pdf(&domain).integrate(&domain) - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn cdf_rises_monotonically_from_zero_to_one() {
+        let domain = unit_domain();
+        let cdf = FuzzySet::triangular("t", 0.2, 0.5, 0.8).to_cdf(&domain);
+        let values = cdf.sample_membership_only(&domain);
+
+        assert!(values.windows(2).all(|pair| pair[1] >= pair[0]));
+        assert_eq!(values[0], 0.0);
+        assert!((values.last().unwrap() - 1.0).abs() < 1e-12);
+        assert_eq!(cdf.membership_degree(2.0), 1.0);
+    }
+
+    #[test]
+    fn pdf_median_matches_centroid_of_symmetric_set() {
+        let domain = Domain::new(0.0, 1.0, 0.001).unwrap();
+        let pdf = FuzzySet::triangular("t", 0.2, 0.5, 0.8).to_pdf(&domain);
+
+        assert!((pdf.percentile(0.5, &domain).unwrap() - pdf.centroid(&domain)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn mamdani_composition_with_its_antecedent_is_bounded_by_consequent() {
+        let antecedent = FuzzySet::triangular("A", 0.2, 0.5, 0.8);
+        let consequent = FuzzySet::gaussian("B", 0.6, 0.1);
+        let inferred = antecedent.mamdani_implication(&consequent).compose_max_min(&antecedent, &unit_domain());
+        let partial = FuzzySet::triangular("A'", 0.0, 0.1, 0.3);
+        let weaker = antecedent.mamdani_implication(&consequent).compose_max_min(&partial, &unit_domain());
+
+        for y in unit_domain().points() {
+            assert!(inferred.membership_degree(y) <= consequent.membership_degree(y) + 1e-12);
+            assert!((inferred.membership_degree(y) - consequent.membership_degree(y)).abs() < 1e-9);
+            assert!(weaker.membership_degree(y) <= f64::min(0.5, consequent.membership_degree(y)) + 1e-9);
+        }
+    }
+
+    #[test]
+    fn larsen_implication_scales_the_consequent() {
+        let antecedent = FuzzySet::triangular("A", 0.2, 0.5, 0.8);
+        let consequent = FuzzySet::gaussian("B", 0.6, 0.1);
+        let relation = antecedent.larsen_implication(&consequent);
+
+        assert!((relation.degree(0.35, 0.6) - 0.5).abs() < 1e-12);
+        assert!((relation.degree(0.5, 0.7) - consequent.membership_degree(0.7)).abs() < 1e-12);
+        assert_eq!(relation.degree(0.9, 0.6), 0.0);
+        let inferred = relation.compose_max_min(&antecedent, &unit_domain());
+        for y in unit_domain().points() {
+            assert!(inferred.membership_degree(y) <= consequent.membership_degree(y) + 1e-12);
+        }
+    }
+
+    #[test]
+    fn compact_set_is_insensitive_to_domain_choice() {
+        let set = FuzzySet::triangular("t", 0.3, 0.5, 0.7);
+        let inner = Domain::new(0.0, 1.0, 0.01).unwrap();
+        let outer = Domain::new(-5.0, 5.0, 0.01).unwrap();
+
+        assert!(set.centroid_domain_sensitivity(&inner, &outer) < 1e-9);
+        assert!(FuzzySet::gaussian("g", 0.2, 0.2).centroid_domain_sensitivity(&inner, &outer) > 0.01);
+    }
+
+    #[test]
+    fn minimal_domain_matches_triangular_support() {
+        let set = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+        let domain = set.minimal_domain(&Domain::new(-1.0, 2.0, 0.001).unwrap(), 1e-9, 0.01).unwrap();
+
+        assert!((domain.min() - 0.2).abs() < 0.0011);
+        assert!((domain.max() - 0.8).abs() < 0.0011);
+        assert_eq!(domain.step(), 0.01);
+        assert!(set.minimal_domain(&unit_domain(), 1.5, 0.01).is_none());
+    }
+
+    #[test]
+    fn rule_weights_round_trip_preserves_inference() {
+        let data = graded_samples(1.0, 0.3);
+        let mut trained = graded_engine(1.0, 1.0);
+        trained.tune_weights(&data, 0.5, 20);
+        let mut restored = graded_engine(1.0, 1.0);
+        restored.set_rule_weights(&trained.get_rule_weights()).unwrap();
+
+        assert_eq!(restored.get_rule_weights(), trained.get_rule_weights());
+        for (input, _) in data {
+            assert_eq!(restored.infer_numeric(input), trained.infer_numeric(input));
+        }
+        assert!(restored.set_rule_weights(&[1.0]).is_err());
+        assert!(restored.set_rule_weights(&[1.0, -1.0]).is_err());
+    }
+
+    #[test]
+    fn normalize_weights_sums_to_one_without_changing_output() {
+        let mut engine = priority_engine();
+        let before: Vec<f64> = [0.2, 0.5, 0.8].iter().map(|&x| engine.infer_numeric(x)).collect();
+        engine.normalize_weights();
+
+        assert!((engine.get_rule_weights().iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert_eq!(engine.get_rule_weights(), vec![0.4, 0.2, 0.4]);
+        for (x, expected) in [0.2, 0.5, 0.8].into_iter().zip(before) {
+            assert!((engine.infer_numeric(x) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn noisy_centroid_stays_close_to_original() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+        let mut uniform = lcg(11);
+        let centroids: Vec<f64> = (0..20)
+            .map(|_| set.apply_noise(&mut uniform, 0.05, &domain).centroid(&domain))
+            .collect();
+        let mean = centroids.iter().sum::<f64>() / centroids.len() as f64;
+
+        assert!((mean - set.centroid(&domain)).abs() < 0.02);
+        assert!(centroids.iter().any(|&c| c != set.centroid(&domain)));
+    }
+
+    #[test]
+    fn zero_noise_is_a_no_op_on_the_grid() {
+        let domain = unit_domain();
+        let set = FuzzySet::gaussian("g", 0.5, 0.1);
+        let noiseless = set.apply_noise(&mut lcg(11), 0.0, &domain);
+
+        for x in domain.points() {
+            assert!((noiseless.membership_degree(x) - set.membership_degree(x)).abs() <= f64::EPSILON);
+        }
+    }
+
+    #[test]
+    fn resampled_triangle_centroid_is_more_accurate_on_a_finer_grid() {
+        let set = FuzzySet::triangular("t", 0.1234, 0.3711, 0.9087);
+        let exact = (0.1234 + 0.3711 + 0.9087) / 3.0;
+        let coarse = Domain::new(0.0, 1.0, 0.1).unwrap();
+        let fine = Domain::new(0.0, 1.0, 0.001).unwrap();
+        let coarse_error = (set.resample(&coarse).centroid(&coarse) - exact).abs();
+        let fine_error = (set.resample(&fine).centroid(&fine) - exact).abs();
+
+        assert!(fine_error < coarse_error);
+        assert!(fine_error < 1e-4);
+    }
+
+    #[test]
+    fn resample_is_idempotent() {
+        let domain = unit_domain();
+        let once = FuzzySet::gaussian("g", 0.5, 0.1).resample(&domain);
+        let twice = once.resample(&domain);
+
+        for x in domain.points().chain([0.123, 0.555]) {
+            assert!((once.membership_degree(x) - twice.membership_degree(x)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn rule_conflict_diagonal_is_one() {
+        let scores = priority_engine().rule_conflict_score(&unit_domain());
+
+        assert_eq!(scores.len(), 3);
+        for (i, row) in scores.iter().enumerate() {
+            assert_eq!(row.len(), 3);
+            assert_eq!(row[i], 1.0);
+        }
+    }
+
+    #[test]
+    fn disjoint_conditions_do_not_conflict() {
+        let scores = priority_engine().rule_conflict_score(&unit_domain());
+
+        assert_eq!(scores[0][2], 0.0);
+        assert_eq!(scores[2][0], 0.0);
+        assert!((scores[0][1] - 30.0 / 60.0).abs() < 0.02);
+        assert_eq!(scores[0][1], scores[1][0]);
+    }
+
+    #[test]
+    fn centroid_at_zero_alpha_is_the_centroid() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("skewed", 0.0, 0.2, 1.0);
+
+        assert_eq!(set.centroid_at_alpha(0.0, &domain), Some(set.centroid(&domain)));
+        assert_eq!(set.centroid_at_alpha(1.5, &domain), None);
+    }
+
+    #[test]
+    fn alpha_centroids_converge_to_the_mode() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("skewed", 0.0, 0.2, 1.0);
+        let distances: Vec<f64> = set
+            .centroids_at_alphas(&[0.0, 0.25, 0.5, 0.75, 0.99], &domain)
+            .into_iter()
+            .map(|centroid| (centroid.unwrap() - 0.2).abs())
+            .collect();
+
+        assert!(distances.windows(2).all(|pair| pair[1] < pair[0]));
+        assert!(distances[4] < 0.01);
+    }
+
+    #[test]
+    fn batch_added_rules_match_one_by_one() {
+        let build = || {
+            vec![
+                FuzzyRule::new(Box::new(|x| x > 0.7), FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3), 1.0),
+                FuzzyRule::new(Box::new(|x| x <= 0.7), FuzzySet::triangular("Medium Priority", 0.1, 0.4, 0.7), 1.0),
+            ]
+        };
+        let mut batch = InferenceEngine::new(Vec::new());
+        batch.add_rules_batch(build());
+        let mut single = InferenceEngine::new(Vec::new());
+        for rule in build() {
+            single.add_rule(rule);
+        }
+        let mut extended = InferenceEngine::new(Vec::new());
+        extended.extend(build());
+
+        assert_eq!(batch.rule_count(), single.rule_count());
+        assert_eq!(extended.rule_count(), 2);
+        for x in [0.2, 0.8] {
+            assert_eq!(batch.infer(x), single.infer(x));
+            assert_eq!(extended.infer_numeric(x), single.infer_numeric(x));
+        }
+    }
+
+    #[test]
+    fn aligned_set_keeps_its_centroid_within_a_step() {
+        let set = FuzzySet::gaussian("g", 0.4321, 0.1);
+        let fine = Domain::new(0.0, 1.0, 0.001).unwrap();
+        let coarse = Domain::new(0.0, 1.0, 0.05).unwrap();
+        let aligned = set.align_to_grid(&coarse);
+
+        assert!((aligned.centroid(&fine) - set.centroid(&fine)).abs() <= coarse.step());
+        for x in coarse.points() {
+            assert!((aligned.membership_degree(x) - set.membership_degree(x)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn aligned_set_interpolates_between_grid_points() {
+        let domain = Domain::new(0.0, 1.0, 0.1).unwrap();
+        let aligned = FuzzySet::gaussian("g", 0.5, 0.2).align_to_grid(&domain);
+
+        for (a, b) in domain.points().zip(domain.points().skip(1)) {
+            let midpoint = (aligned.membership_degree(a) + aligned.membership_degree(b)) / 2.0;
+            assert!((aligned.membership_degree((a + b) / 2.0) - midpoint).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn validate_accepts_a_healthy_rule_base() {
+        assert!(priority_engine().validate().is_ok());
+    }
+
+    #[test]
+    fn validate_reports_every_failure() {
+        let urgent = FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3);
+        let half = FuzzySet::from_points("Half", &[(0.0, 0.0), (0.5, 0.5), (1.0, 0.0)]).unwrap();
+
+        assert!(matches!(
+            InferenceEngine::new(Vec::new()).validate().err().as_deref(),
+            Some([FuzzyError::NoRules])
+        ));
+
+        let zero_weights = InferenceEngine::new(vec![
+            FuzzyRule::new(Box::new(|x| x > 0.5), urgent.clone(), 0.0),
+            FuzzyRule::new(Box::new(|x| x <= 0.5), urgent.clone(), 0.0),
+        ]);
+        assert!(matches!(
+            zero_weights.validate().err().as_deref(),
+            Some([FuzzyError::NonPositiveTotalWeight(total)]) if *total == 0.0
+        ));
+
+        let mixed = InferenceEngine::new(vec![
+            FuzzyRule::new(Box::new(|x| x > 0.5), urgent, f64::NAN),
+            FuzzyRule::new(Box::new(|x| x <= 0.5), half, 1.0),
+        ]);
+        let errors = mixed.validate().err().unwrap();
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(errors[0], FuzzyError::InvalidWeight(_)));
+        assert!(matches!(errors[1], FuzzyError::NotNormal { height } if height == 0.5));
+        assert!(matches!(errors[2], FuzzyError::NonPositiveTotalWeight(_)));
+
+        let flat = FuzzySet::from_points("Flat", &[(0.0, 0.0), (1.0, 0.0)]).unwrap();
+        let empty = InferenceEngine::new(vec![FuzzyRule::new(Box::new(|_| true), flat, 1.0)]);
+        assert!(matches!(empty.validate().err().as_deref(), Some([FuzzyError::EmptySupport])));
+    }
+
+    #[test]
+    fn invalid_rule_base_falls_back_to_default_output() {
+        let mut engine = priority_engine();
+        for rule in &mut engine.rules {
+            rule.weight = 0.0;
+        }
+
+        assert_eq!(engine.try_infer(0.8).is_err(), cfg!(debug_assertions));
+        assert_eq!(engine.infer(0.8), "Low Priority");
+    }
+
+    #[test]
+    fn identical_sets_share_a_checksum() {
+        let domain = unit_domain();
+        let a = FuzzySet::triangular("a", 0.2, 0.5, 0.8);
+        let b = FuzzySet::triangular("renamed", 0.2, 0.5, 0.8);
+
+        assert_eq!(a.checksum(&domain), b.checksum(&domain));
+        assert_eq!(a.checksum(&domain), a.clone().checksum(&domain));
+    }
+
+    #[test]
+    fn shifted_set_changes_its_checksum() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+
+        assert_ne!(set.checksum(&domain), set.shift(0.001).checksum(&domain));
+    }
+
+    #[test]
+    fn membership_histogram_covers_the_unit_interval() {
+        let domain = unit_domain();
+        let bins = FuzzySet::gaussian("g", 0.5, 0.2).membership_histogram(4, &domain);
+
+        assert_eq!(bins.len(), 4);
+        assert_eq!(bins.iter().map(|&(_, _, count)| count).sum::<usize>(), domain.points().count());
+        assert_eq!(bins[0].0, 0.0);
+        assert_eq!(bins[3].1, 1.0);
+        assert!(bins.windows(2).all(|pair| pair[0].1 == pair[1].0));
+    }
+
+    #[test]
+    fn constant_set_fills_a_single_bin() {
+        let domain = unit_domain();
+        let bins = FuzzySet::new("half", Arc::new(|_| 0.5)).membership_histogram(10, &domain);
+
+        assert_eq!(bins[5].2, domain.points().count());
+        assert!(bins.iter().enumerate().all(|(i, bin)| i == 5 || bin.2 == 0));
+        assert!(FuzzySet::new("half", Arc::new(|_| 0.5)).membership_histogram(0, &domain).is_empty());
+    }
+
+    #[test]
+    fn infer_streaming_stops_at_first_confident_input() {
+        let engine = priority_engine();
+        let consumed = std::cell::Cell::new(0);
+        let inputs = [0.8, 0.9, 0.5, 0.2].into_iter().inspect(|_| consumed.set(consumed.get() + 1));
+
+        assert_eq!(engine.infer_streaming(inputs, 0.9), Some(("High Priority".to_string(), 1.0)));
+        assert_eq!(consumed.get(), 3);
+        assert_eq!(engine.infer_streaming([0.8, 0.9], 0.9), None);
+    }
+
+    #[test]
+    fn infer_all_streaming_processes_every_input() {
+        let engine = priority_engine();
+        let results: Vec<(String, f64)> = engine.infer_all_streaming(vec![0.2, 0.5, 0.8]).collect();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], ("Medium Priority".to_string(), 1.0));
+        assert_eq!(results[2].0, "Urgent");
+        assert!((results[2].1 - 2.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn norm_aliases_match_their_definitions() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+
+        assert_eq!(set.l_infinity_norm(&domain), set.height(&domain));
+        assert_eq!(set.l1_norm(&domain), set.integrate(&domain));
+        assert!((set.l2_norm(&domain) - (0.6f64 / 3.0).sqrt()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn norms_satisfy_the_unit_domain_inequalities() {
+        let domain = unit_domain();
+        let length = domain.width();
+
+        for set in [
+            FuzzySet::triangular("t", 0.2, 0.5, 0.8),
+            FuzzySet::gaussian("g", 0.3, 0.2),
+            FuzzySet::trapezoidal("z", 0.1, 0.3, 0.6, 0.9),
+        ] {
+            let (l_inf, l1, l2) = (set.l_infinity_norm(&domain), set.l1_norm(&domain), set.l2_norm(&domain));
+            assert!(l_inf >= l2);
+            assert!(l2 >= l1 / length.sqrt());
+        }
+    }
+
+    #[test]
+    fn shape_statistics_match_direct_moment_sums() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("skewed", 0.0, 0.2, 1.0);
+        let samples = set.sample(&domain);
+        let total: f64 = samples.iter().map(|&(_, mu)| mu).sum();
+        let central = |n: i32, center: f64| samples.iter().map(|&(x, mu)| (x - center).powi(n) * mu).sum::<f64>() / total;
+        let mean = central(1, 0.0);
+        let variance = central(2, mean);
+
+        assert!((set.centroid(&domain) - mean).abs() < 1e-12);
+        assert!((set.membership_variance(&domain) - variance).abs() < 1e-12);
+        assert!((set.skewness(&domain) - central(3, mean) / variance.powf(1.5)).abs() < 1e-9);
+        assert!((set.kurtosis(&domain) - central(4, mean) / variance.powi(2)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn zeroth_moment_is_one_and_empty_set_moments_are_zero() {
+        let domain = unit_domain();
+
+        assert!((FuzzySet::gaussian("g", 0.5, 0.1).moment(0, 0.3, &domain) - 1.0).abs() < 1e-12);
+        assert_eq!(FuzzySet::new("empty", Arc::new(|_| 0.0)).moment(2, 0.5, &domain), 0.0);
+    }
+
+    fn label_to_level(label: String) -> f64 {
+        match label.as_str() {
+            "Urgent" => 0.9,
+            "High Priority" => 0.6,
+            _ => 0.3,
+        }
+    }
+
+    fn inverting_engine() -> InferenceEngine {
+        InferenceEngine::new(vec![
+            FuzzyRule::new(Box::new(|x| x > 0.5), FuzzySet::triangular("Medium Priority", 0.1, 0.4, 0.7), 1.0),
+            FuzzyRule::new(Box::new(|x| x <= 0.5), FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3), 1.0),
+        ])
+    }
+
+    #[test]
+    fn chain_output_depends_on_the_intermediate_label() {
+        let chain = InferenceEngineChain::new(priority_engine()).then(label_to_level, inverting_engine());
+
+        assert_eq!(chain.stage_count(), 2);
+        assert_eq!(chain.chain_infer(0.2), vec!["Medium Priority", "Urgent"]);
+        assert_eq!(chain.chain_infer(0.8), vec!["Urgent", "Medium Priority"]);
+    }
+
+    #[test]
+    fn explain_chain_feeds_each_stage_the_mapped_output() {
+        let chain = InferenceEngineChain::new(priority_engine()).then(label_to_level, inverting_engine());
+        let traces = chain.explain_chain(0.5);
+
+        assert_eq!(traces.len(), 2);
+        assert_eq!(traces[0].input, 0.5);
+        assert_eq!(traces[0].output, "High Priority");
+        assert_eq!(traces[1].input, 0.6);
+        assert_eq!(traces[1].output, "Medium Priority");
+    }
+
+    #[test]
+    fn triangular_lipschitz_constant_is_its_steepest_slope() {
+        let domain = unit_domain();
+
+        for (a, b, c) in [(0.2, 0.5, 0.8), (0.1, 0.3, 0.9), (0.0, 0.8, 1.0)] {
+            let expected = f64::max(1.0 / (b - a), 1.0 / (c - b));
+            let estimate = FuzzySet::triangular("t", a, b, c).estimate_lipschitz_constant(&domain);
+            assert!((estimate - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn is_lipschitz_compares_against_the_estimate() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("t", 0.1, 0.3, 0.9);
+
+        assert!(set.is_lipschitz(5.0, &domain));
+        assert!(!set.is_lipschitz(4.0, &domain));
+        assert!(FuzzySet::new("flat", Arc::new(|_| 0.5)).is_lipschitz(0.0, &domain));
+    }
+
+    fn overlapping_engine() -> InferenceEngine {
+        InferenceEngine::new(vec![
+            FuzzyRule::new(Box::new(|x| x > 0.1), FuzzySet::triangular("Medium Priority", 0.1, 0.4, 0.7), 0.2),
+            FuzzyRule::new(Box::new(|x| x > 0.4), FuzzySet::triangular("High Priority", 0.4, 0.7, 1.0), 1.0),
+            FuzzyRule::new(Box::new(|x| x > 0.7), FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3), 0.3),
+        ])
+    }
+
+    #[test]
+    fn inference_modes_differ_when_several_rules_fire() {
+        let run = |mode| {
+            let engine = overlapping_engine().with_inference_mode(mode);
+            (engine.infer(0.8), engine.infer_numeric(0.8))
+        };
+
+        assert_eq!(run(InferenceMode::FirstMatch), ("Medium Priority".to_string(), 1.0));
+        assert_eq!(run(InferenceMode::LastMatch), ("Urgent".to_string(), 3.0));
+        assert_eq!(run(InferenceMode::BestMatch), ("High Priority".to_string(), 2.0));
+        let (label, score) = run(InferenceMode::WeightedAggregate);
+        assert_eq!(label, "High Priority");
+        assert!((score - 3.1 / 1.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn inference_modes_agree_when_one_rule_fires() {
+        for mode in [
+            InferenceMode::WeightedAggregate,
+            InferenceMode::FirstMatch,
+            InferenceMode::LastMatch,
+            InferenceMode::BestMatch,
+        ] {
+            let engine = overlapping_engine().with_inference_mode(mode);
+            assert_eq!(engine.infer(0.2), "Medium Priority");
+            assert_eq!(engine.infer_numeric(0.2), 1.0);
+            assert_eq!(engine.trace(0.2).fired_rules.len(), 1);
+        }
+    }
+
+    #[test]
+    fn bimodal_union_has_one_centroid_per_mode() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("a", 0.1, 0.2, 0.3).union(&FuzzySet::triangular("b", 0.6, 0.7, 0.8));
+        let centroids = set.multi_centroid(&domain);
+
+        assert_eq!(centroids.len(), 2);
+        assert!((centroids[0] - 0.2).abs() < 1e-9);
+        assert!((centroids[1] - 0.7).abs() < 1e-9);
+        assert!(set.membership_degree(set.centroid(&domain)) == 0.0);
+    }
+
+    #[test]
+    fn convex_set_has_a_single_centroid() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("skewed", 0.0, 0.2, 1.0);
+
+        assert_eq!(set.multi_centroid(&domain), vec![set.centroid(&domain)]);
+        assert!(FuzzySet::new("empty", Arc::new(|_| 0.0)).multi_centroid(&domain).is_empty());
+    }
+
+    #[test]
+    fn universal_weight_gives_the_standard_bisector() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("skewed", 0.0, 0.2, 1.0);
+        let universal = FuzzySet::new("universal", Arc::new(|_| 1.0));
+
+        assert_eq!(set.defuzzify_weighted_bisector(&universal, &domain), set.defuzzify_bisector(&domain));
+    }
+
+    #[test]
+    fn rising_weight_shifts_the_bisector_right() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+        let rising = FuzzySet::new("rising", Arc::new(|x: f64| x.clamp(0.0, 1.0)));
+        let falling = FuzzySet::new("falling", Arc::new(|x: f64| (1.0 - x).clamp(0.0, 1.0)));
+        let standard = set.defuzzify_bisector(&domain).unwrap();
+
+        assert!(set.defuzzify_weighted_bisector(&rising, &domain).unwrap() > standard);
+        assert!(set.defuzzify_weighted_bisector(&falling, &domain).unwrap() < standard);
+        assert_eq!(set.defuzzify_weighted_bisector(&FuzzySet::new("none", Arc::new(|_| 0.0)), &domain), None);
+    }
+
+    fn band_area((lower, upper): &(FuzzySet, FuzzySet), domain: &Domain) -> f64 {
+        domain
+            .points()
+            .map(|x| upper.membership_degree(x) - lower.membership_degree(x))
+            .sum::<f64>()
+            * domain.step()
+    }
+
+    #[test]
+    fn sensitivity_band_brackets_the_original_set() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+        let (lower, upper) = set.parameter_sensitivity_band(0, 0.05).unwrap();
+
+        for x in domain.points() {
+            assert!(lower.membership_degree(x) <= set.membership_degree(x) + 1e-12);
+            assert!(set.membership_degree(x) <= upper.membership_degree(x) + 1e-12);
+        }
+        assert!(band_area(&set.parameter_sensitivity_band(1, 0.0).unwrap(), &domain) < 1e-12);
+    }
+
+    #[test]
+    fn sensitivity_band_is_wider_for_influential_parameters() {
+        let domain = unit_domain();
+        let set = FuzzySet::sigmoidal("s", 40.0, 0.5);
+        let slope_band = band_area(&set.parameter_sensitivity_band(0, 2.0).unwrap(), &domain);
+        let center_band = band_area(&set.parameter_sensitivity_band(1, 0.05).unwrap(), &domain);
+        let triangle = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+
+        assert!(center_band > 5.0 * slope_band);
+        assert!(
+            band_area(&triangle.parameter_sensitivity_band(1, 0.1).unwrap(), &domain)
+                > band_area(&triangle.parameter_sensitivity_band(1, 0.02).unwrap(), &domain)
+        );
+    }
+
+    #[test]
+    fn sensitivity_band_rejects_unparameterised_sets() {
+        let custom = FuzzySet::new("custom", Arc::new(|_| 0.5));
+
+        assert!(custom.parameter_sensitivity_band(0, 0.1).is_err());
+        assert!(FuzzySet::triangular("t", 0.2, 0.5, 0.8).parameter_sensitivity_band(3, 0.1).is_err());
+    }
+
+    #[test]
+    fn perturbed_weights_stay_in_the_unit_interval() {
+        let engine = priority_engine();
+        let mut uniform = lcg(5);
+
+        assert_eq!(engine.clone_with_perturbed_weights(&mut uniform, 0.0).get_rule_weights(), engine.get_rule_weights());
+        for _ in 0..50 {
+            let perturbed = engine.clone_with_perturbed_weights(&mut uniform, 1.0);
+            assert!(perturbed.get_rule_weights().iter().all(|w| (0.0..=1.0).contains(w)));
+        }
+    }
+
+    #[test]
+    fn monte_carlo_interval_widens_with_sigma() {
+        let engine = priority_engine();
+        let (label, still) = engine.monte_carlo_infer(&mut lcg(5), 0.8, 500, 0.0);
+        let (_, narrow) = engine.monte_carlo_infer(&mut lcg(5), 0.8, 500, 0.05);
+        let (_, wide) = engine.monte_carlo_infer(&mut lcg(5), 0.8, 500, 0.3);
+
+        assert_eq!(label, "Urgent");
+        assert_eq!(still, 0.0);
+        assert!(narrow > 0.0);
+        assert!(wide > narrow);
+    }
+
+    #[test]
+    fn parametric_identity_curve_reproduces_a_triangle() {
+        let triangle = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+        let set = FuzzySet::from_parametric("p", |t| t, |t| triangle.membership_degree(t), 101).unwrap();
+
+        for x in unit_domain().points().chain([0.333, 0.777]) {
+            assert!((set.membership_degree(x) - triangle.membership_degree(x)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn parametric_curve_is_sorted_and_validated() {
+        let reversed = FuzzySet::from_parametric("r", |t| 1.0 - t, |t| t, 11).unwrap();
+
+        assert!((reversed.membership_degree(0.25) - 0.75).abs() < 1e-9);
+        assert!(matches!(
+            FuzzySet::from_parametric("bad", |t| t, |t| 2.0 * t, 11),
+            Err(FuzzyError::InvalidPoints(_))
+        ));
+    }
+
+    #[test]
+    fn ratio_of_a_set_with_itself_is_one_on_its_support() {
+        let set = FuzzySet::gaussian("g", 0.5, 0.1);
+        let ratio = set.membership_ratio(&set);
+
+        for x in unit_domain().points() {
+            assert_eq!(ratio(x), 1.0);
+        }
+    }
+
+    #[test]
+    fn ratio_handles_zero_memberships() {
+        let a = FuzzySet::triangular("a", 0.2, 0.5, 0.8);
+        let b = FuzzySet::triangular("b", 0.4, 0.6, 0.8);
+        let ratio = a.membership_ratio(&b);
+
+        assert_eq!(ratio(0.9), 1.0);
+        assert_eq!(ratio(0.3), f64::MAX);
+        assert_eq!(b.membership_ratio(&a)(0.3), 0.0);
+        assert!((ratio(0.5) - (1.0 / 0.5)).abs() < 1e-12);
+    }
+
+    fn weak_engine() -> InferenceEngine {
+        InferenceEngine::new(vec![
+            FuzzyRule::new(Box::new(|x| x > 0.7), FuzzySet::triangular("Medium Priority", 0.1, 0.4, 0.7), 0.1)
+                .with_name("very high"),
+            FuzzyRule::new(Box::new(|x| x <= 0.4), FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3), 0.1)
+                .with_name("quiet"),
+        ])
+    }
+
+    #[test]
+    fn merged_engine_keeps_every_rule_and_the_dominant_output() {
+        let base = priority_engine();
+        let merged = base.merge(&weak_engine());
+
+        assert_eq!(merged.rule_count(), base.rule_count() + weak_engine().rule_count());
+        assert_eq!(merged.get_rule_weights(), vec![1.0, 0.5, 1.0, 0.1, 0.1]);
+        for x in [0.2, 0.5, 0.8] {
+            assert_eq!(merged.infer(x), base.infer(x));
+        }
+    }
+
+    #[test]
+    fn merge_disambiguates_names_and_subtract_removes_them() {
+        let merged = priority_engine().merge(&weak_engine()).merge(&weak_engine());
+        let names: Vec<Option<&str>> = merged.rules.iter().map(|rule| rule.name()).collect();
+
+        assert_eq!(names[3], Some("very high (2)"));
+        assert_eq!(names[5], Some("very high (3)"));
+        assert_eq!(names[6], Some("quiet (2)"));
+
+        let remaining = priority_engine().merge(&weak_engine()).subtract_rules(&weak_engine());
+        let names: Vec<Option<&str>> = remaining.rules.iter().map(|rule| rule.name()).collect();
+        assert_eq!(names, vec![Some("high"), Some("low"), Some("very high (2)")]);
+    }
+
+    fn sine_set(frequency: f64) -> FuzzySet {
+        FuzzySet::new(
+            "sine",
+            Arc::new(move |x: f64| 0.5 + 0.5 * (2.0 * std::f64::consts::PI * frequency * x).sin()),
+        )
+    }
+
+    #[test]
+    fn dominant_frequency_matches_a_sine_membership() {
+        let domain = Domain::new(0.0, 0.99, 0.01).unwrap();
+
+        for frequency in [3.0, 5.0, 12.0] {
+            assert!((sine_set(frequency).dominant_frequency(&domain) - frequency).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn dft_returns_the_lowest_frequency_coefficients() {
+        let domain = Domain::new(0.0, 0.99, 0.01).unwrap();
+        let coefficients = sine_set(5.0).dft_coefficients(&domain, 8);
+
+        assert_eq!(coefficients.len(), 8);
+        assert!((coefficients[0].0 - 50.0).abs() < 1e-9);
+        assert!((coefficients[5].0.hypot(coefficients[5].1) - 25.0).abs() < 1e-9);
+        assert!(coefficients[3].0.hypot(coefficients[3].1) < 1e-9);
+    }
+
+    #[test]
+    fn low_pass_filter_preserves_a_triangle() {
+        let domain = Domain::new(0.0, 0.99, 0.01).unwrap();
+        let set = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+        let filtered = set.low_pass_filter(&domain, 10.0);
+
+        for x in domain.points() {
+            assert!((filtered.membership_degree(x) - set.membership_degree(x)).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    fn low_pass_filter_attenuates_high_frequencies() {
+        let domain = Domain::new(0.0, 0.99, 0.01).unwrap();
+        let filtered = sine_set(20.0).low_pass_filter(&domain, 5.0);
+
+        for x in domain.points() {
+            assert!((filtered.membership_degree(x) - 0.5).abs() < 1e-9);
+            assert!((0.0..=1.0).contains(&filtered.membership_degree(x)));
+        }
+    }
+
+    #[test]
+    fn zero_lag_autocorrelation_is_the_squared_l2_norm() {
+        let domain = unit_domain();
+
+        for set in [FuzzySet::triangular("t", 0.2, 0.5, 0.8), FuzzySet::gaussian("g", 0.5, 0.1)] {
+            let autocorrelation = set.autocorrelation(&domain);
+            assert_eq!(autocorrelation[0].0, 0.0);
+            assert!((autocorrelation[0].1 - set.l2_norm(&domain).powi(2)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn smooth_autocorrelation_decays_and_cross_correlation_peaks_at_the_offset() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("t", 0.1, 0.3, 0.5);
+        let autocorrelation = set.autocorrelation(&domain);
+        let shifted = set.shift(0.4);
+        let cross = set.correlation(&shifted, &domain);
+        let peak = cross.iter().max_by(|a, b| a.1.partial_cmp(&b.1).unwrap()).unwrap();
+
+        assert_eq!(autocorrelation.len(), domain.sample_count());
+        assert!(autocorrelation.windows(2).all(|pair| pair[1].1 <= pair[0].1 + 1e-12));
+        assert!((peak.0 - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pid_step_response_converges_to_the_setpoint() {
+        let mut controller = FuzzyPIDController::new(graded_engine(1.0, 1.0), 1.0, 1.0, 0.0);
+        let (setpoint, dt) = (0.5, 0.05);
+        let mut output = 0.0;
+
+        for _ in 0..600 {
+            let effort = controller.compute_output(setpoint - output, dt) - 1.0;
+            output += dt * (effort - output);
+        }
+
+        assert!((output - setpoint).abs() < 1e-3);
+        assert!(controller.integral() > 0.0);
+    }
+
+    #[test]
+    fn zero_gains_disable_their_terms() {
+        let engine = || graded_engine(1.0, 1.0);
+        let mut silent = FuzzyPIDController::new(engine(), 0.0, 0.0, 0.0);
+        let mut proportional = FuzzyPIDController::new(engine(), 0.5, 0.0, 0.0);
+        let mut integral = FuzzyPIDController::new(engine(), 0.0, 0.5, 0.0);
+        let mut derivative = FuzzyPIDController::new(engine(), 0.0, 0.0, 0.1);
+
+        let idle = engine().infer_numeric(0.0);
+        assert_eq!(silent.compute_output(0.4, 0.1), idle);
+        assert_eq!(silent.compute_output(-0.3, 0.1), idle);
+
+        let first = proportional.compute_output(0.4, 0.1);
+        assert_eq!(proportional.compute_output(0.4, 0.1), first);
+        assert_eq!(first, engine().infer_numeric(0.2));
+
+        let first = integral.compute_output(0.4, 0.1);
+        assert!(integral.compute_output(0.4, 0.1) > first);
+
+        assert_eq!(derivative.compute_output(0.4, 0.1), idle);
+        assert_eq!(derivative.compute_output(0.9, 0.1), engine().infer_numeric(0.5));
+        derivative.reset();
+        assert_eq!(derivative.integral(), 0.0);
+    }
+
+    #[test]
+    fn union_and_intersection_commute_robustly() {
+        let domain = unit_domain();
+        let a = FuzzySet::triangular("a", 0.1, 0.4, 0.7);
+        let b = FuzzySet::gaussian("b", 0.6, 0.15);
+
+        assert!(a.union(&b).membership_equal_robust(&b.union(&a), &domain));
+        assert!(a.intersection(&b).membership_equal_robust(&b.intersection(&a), &domain));
+        assert!(!a.membership_equal_robust(&b, &domain));
+    }
+
+    #[test]
+    fn de_morgan_laws_hold_robustly() {
+        let domain = unit_domain();
+        let a = FuzzySet::triangular("a", 0.1, 0.4, 0.7);
+        let b = FuzzySet::gaussian("b", 0.6, 0.15);
+
+        assert!(a
+            .union(&b)
+            .complement()
+            .membership_equal_robust(&a.complement().intersection(&b.complement()), &domain));
+        assert!(a
+            .intersection(&b)
+            .complement()
+            .membership_equal_robust(&a.complement().union(&b.complement()), &domain));
+    }
+
+    #[test]
+    fn alpha_cut_areas_integrate_to_scalar_cardinality() {
+        let domain = Domain::new(0.0, 1.0, 0.001).unwrap();
+        let levels = 1000;
+
+        for set in [FuzzySet::triangular("t", 0.2, 0.5, 0.8), FuzzySet::gaussian("g", 0.5, 0.1)] {
+            let integral: f64 = (0..levels)
+                .map(|i| set.area_above_alpha((i as f64 + 0.5) / levels as f64, &domain) / levels as f64)
+                .sum();
+            let cardinality = set.scalar_cardinality(&domain);
+            assert!((integral - cardinality).abs() < 1e-3 * cardinality);
+        }
+    }
+
+    #[test]
+    fn extreme_alpha_areas_are_support_and_core_widths() {
+        let domain = unit_domain();
+        let set = FuzzySet::trapezoidal("z", 0.1, 0.3, 0.6, 0.9);
+
+        let core = set.core(&domain);
+
+        assert!((set.area_above_alpha(0.0, &domain) - 0.8).abs() < 1e-9);
+        assert!((set.area_above_alpha(0.0, &domain) - set.support_width(&domain)).abs() < 1e-12);
+        assert!((set.area_above_alpha(1.0, &domain) - 0.3).abs() < 1e-9);
+        assert!((set.area_above_alpha(1.0, &domain) - (core[core.len() - 1] - core[0])).abs() < 1e-12);
+        assert_eq!(set.area_above_alpha(1.5, &domain), 0.0);
+    }
+
+    #[test]
+    fn dot_graph_is_well_formed_with_one_node_per_rule() {
+        let dot = priority_engine().to_dot_graph(&[&["load"], &["load"], &["temperature"]]);
+        let body: Vec<&str> = dot.lines().skip(1).take_while(|line| *line != "}").collect();
+
+        assert!(dot.starts_with("digraph rules {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(body.iter().all(|line| line.starts_with("    ") && line.ends_with(';')));
+        assert_eq!(dot.matches("shape=box").count(), 3);
+        assert_eq!(dot.matches("shape=ellipse").count(), 2);
+        assert_eq!(dot.matches("shape=doublecircle").count(), 3);
+        assert!(dot.contains("rule_0 [label=\"very high\", shape=box];"));
+    }
+
+    #[test]
+    fn rules_sharing_an_input_share_its_node() {
+        let dot = priority_engine().to_dot_graph(&[&["load"], &["load"], &["temperature"]]);
+
+        assert!(dot.contains("input_0 [label=\"load\", shape=ellipse];"));
+        assert!(dot.contains("input_0 -> rule_0;"));
+        assert!(dot.contains("input_0 -> rule_1;"));
+        assert!(dot.contains("input_1 -> rule_2;"));
+        assert!(!dot.contains("input_1 -> rule_0;"));
+        assert_eq!(dot.matches("-> output_").count(), 3);
+    }
+
+    #[test]
+    fn functional_derivative_of_cardinality_is_the_step() {
+        let domain = unit_domain();
+        let gradient = FuzzySet::triangular("t", 0.2, 0.5, 0.8).functional_derivative(
+            |set, domain| set.scalar_cardinality(domain),
+            &domain,
+            1e-6,
+        );
+
+        for x in domain.points() {
+            assert!((gradient.membership_degree(x) - domain.step()).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn functional_derivative_points_towards_a_higher_objective() {
+        let domain = unit_domain();
+        let set = FuzzySet::triangular("t", 0.2, 0.5, 0.8);
+        let objective = |set: &FuzzySet, domain: &Domain| set.centroid(domain);
+        let gradient = set.functional_derivative(objective, &domain, 1e-6);
+        let ascended = set.compose(&gradient, |mu, g| mu + 0.1 * g, "ascended");
+
+        assert!(gradient.membership_degree(0.3) < 0.0);
+        assert!(gradient.membership_degree(0.7) > 0.0);
+        assert!(objective(&ascended, &domain) > objective(&set, &domain));
+    }
+
+    #[test]
+    fn rules_are_grouped_by_consequence() {
+        let mut engine = priority_engine();
+        engine.add_rule(
+            FuzzyRule::new(Box::new(|x| x > 0.9), FuzzySet::triangular("Urgent", 0.7, 1.0, 1.3), 1.0)
+                .with_name("extreme"),
+        );
+        let groups = engine.rules_grouped_by_consequence();
+        let names = |consequence: &str| -> Vec<Option<&str>> {
+            engine.rules_for_consequence(consequence).iter().map(|rule| rule.name()).collect()
+        };
+
+        assert_eq!(names("Urgent"), vec![Some("very high"), Some("extreme")]);
+        assert_eq!(names("High Priority"), vec![Some("high")]);
+        assert_eq!(names("Medium Priority"), vec![Some("low")]);
+        assert!(names("Low Priority").is_empty());
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups["Urgent"].len(), 2);
+        assert_eq!(groups["Medium Priority"][0].name(), Some("low"));
+    }
+
+    #[test]
+    fn consequence_counts_sum_to_the_rule_count() {
+        let counts = priority_engine().consequence_counts();
+
+        assert_eq!(counts.values().sum::<usize>(), priority_engine().rule_count());
+        assert_eq!(counts["Urgent"], 1);
+        assert_eq!(counts["High Priority"], 1);
+        assert_eq!(counts["Medium Priority"], 1);
+    }
+
+    #[test]
+    fn never_firing_rule_still_has_unit_diagonal() {
+        let mut engine = priority_engine();
+        engine.add_rule(FuzzyRule::new(
+            Box::new(|_| false),
+            FuzzySet::triangular("Never", 0.0, 0.5, 1.0),
+            1.0,
+        ));
+        let scores = engine.rule_conflict_score(&unit_domain());
+
+        assert_eq!(scores[3][3], 1.0);
+        assert!(scores[3][..3].iter().all(|&score| score == 0.0));
+    }
+
+    #[test]
+    fn tune_weights_loss_goes_through_the_output_transformer() {
+        let transformer: Arc<dyn Fn(f64) -> f64 + Send + Sync> = Arc::new(|x| 2.0 * x + 1.0);
+        let target = graded_engine(1.0, 0.3).with_output_transformer(Arc::clone(&transformer));
+        let data: Vec<(f64, f64)> = (1..10).map(|i| i as f64 / 10.0).map(|x| (x, target.infer_numeric(x))).collect();
+        let mut engine = graded_engine(1.0, 1.0).with_output_transformer(transformer);
+        let initial: f64 = data
+            .iter()
+            .map(|&(x, y)| (engine.infer_numeric(x) - y).powi(2))
+            .sum::<f64>()
+            / data.len() as f64;
+        let losses = engine.tune_weights(&data, 0.1, 50);
+
+        assert!((losses[0] - initial).abs() < 1e-12);
+        assert!(losses.windows(2).all(|pair| pair[1] <= pair[0] + 1e-12));
+        assert!(losses[49] < losses[0] / 10.0);
+    }
+
+    #[test]
+    fn tune_weights_leaves_single_rule_modes_unchanged() {
+        let data = graded_samples(1.0, 0.3);
+
+        for mode in [InferenceMode::FirstMatch, InferenceMode::LastMatch, InferenceMode::BestMatch] {
+            let mut engine = graded_engine(1.0, 1.0).with_inference_mode(mode);
+            let losses = engine.tune_weights(&data, 0.5, 5);
+
+            assert!(losses.windows(2).all(|pair| (pair[0] - pair[1]).abs() < 1e-12));
+            assert!(engine.get_rule_weights().iter().all(|weight| (weight - 1.0).abs() < 1e-9));
+        }
+    }
+
+    #[test]
+    fn partial_ord_compares_centroids_on_the_default_domain() {
+        let lower = FuzzySet::triangular("lower", 0.0, 0.2, 0.4);
+        let upper = FuzzySet::triangular("upper", 0.1, 0.3, 0.5);
+
+        assert!(lower < upper);
+        assert!(upper > lower);
+        assert!(lower == FuzzySet::triangular("renamed", 0.0, 0.2, 0.4));
+        assert_eq!(Domain::default(), Domain::new(0.0, 1.0, 0.01).unwrap());
+    }
+
+    #[test]
+    fn rank_centroid_is_consistent_with_partial_ord() {
+        let domain = Domain::default();
+        let sets = [
+            FuzzySet::triangular("t", 0.0, 0.2, 0.4),
+            FuzzySet::triangular("u", 0.1, 0.3, 0.5),
+            FuzzySet::gaussian("g", 0.6, 0.1),
+            FuzzySet::trapezoidal("z", 0.5, 0.7, 0.8, 1.0),
+        ];
+
+        for a in &sets {
+            for b in &sets {
+                assert_eq!(a.partial_cmp(b), Some(a.rank_centroid(b, &domain)));
+            }
+        }
+    }
+
+    #[test]
+    fn registry_builds_named_rules_and_reports_missing_entries() {
+        let registry = priority_registry();
+        let rule = registry.build_rule("high", "High Priority", 0.8).unwrap();
+
+        assert_eq!(rule.name(), Some("high"));
+        assert_eq!(rule.firing_strength(0.7), 1.0);
+        assert_eq!(
+            registry.build_rule("high", "Missing", 1.0).err(),
+            Some(FuzzyError::UnknownConsequence("Missing".to_string()))
+        );
+        assert_eq!(
+            registry.build_rule("missing", "Urgent", 1.0).err(),
+            Some(FuzzyError::UnknownCondition("missing".to_string()))
+        );
     }
 }